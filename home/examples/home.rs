@@ -3,7 +3,7 @@ use home::devices::thermometer::Thermometer;
 use home::places::{Home, Room};
 
 fn main() {
-    let mut home = Home::new("home");
+    let home = Home::new("home");
 
     let room_name_01 = "R01";
     let room01 = Room::new(room_name_01);
@@ -0,0 +1,50 @@
+use home::devices::socket::Socket;
+use home::devices::thermometer::Thermometer;
+use home::places::{Home, Room};
+
+fn main() {
+    let home = Home::new("home");
+
+    home.add_room(Room::new("Kitchen")).unwrap();
+    home.add_room(Room::new("Hallway")).unwrap();
+
+    home.add_device("Kitchen", Box::new(Socket::from("S01", "Description S01", 1500.0)))
+        .unwrap();
+    home.add_device("Kitchen", Box::new(Socket::from("S02", "Description S02", 1000.0)))
+        .unwrap();
+    home.add_device("Kitchen", Box::new(Thermometer::from("T01", "Description of T01")))
+        .unwrap();
+    home.add_device("Hallway", Box::new(Socket::from("S03", "Description S03", 500.0)))
+        .unwrap();
+    home.add_device("Hallway", Box::new(Thermometer::from("T02", "Description of T02")))
+        .unwrap();
+
+    home.connect_device("Kitchen", "S01", "T01").unwrap();
+    home.connect_device("Hallway", "S03", "T02").unwrap();
+
+    println!("\nInitial schema of the Home.");
+    home.print_schema();
+
+    println!("\nMove T01 from S01 to S02.");
+    home.reparent_device("T01", "S02").unwrap();
+    home.print_schema();
+
+    println!("\nRename S02 to Main socket.");
+    home.rename_device("S02", "Main socket").unwrap();
+    home.print_schema();
+
+    println!("\nRemove S01.");
+    home.remove_device("S01").unwrap();
+    home.print_schema();
+
+    println!("\nRemove Main socket with connected devices.");
+    let removed = home.remove_device_cascade("Main socket").unwrap();
+    println!("Removed devices: {}", removed.join(", "));
+    home.print_schema();
+
+    println!("\nRemove room Hallway.");
+    home.remove_room("Hallway").unwrap();
+    home.print_schema();
+
+    println!("\nHome is consistent: {}", home.validate().is_empty());
+}
@@ -1,16 +1,138 @@
+pub mod power_strip;
+pub mod registry;
 pub mod socket;
 pub mod thermometer;
 
+use std::{fmt, str::FromStr};
+
+use power_strip::PowerStrip;
+use socket::Socket;
+
+/// Kind of the device
+///
+/// Used to tell apart devices, which are stored as trait objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceKind {
+    Socket,
+    Thermometer,
+    PowerStrip,
+    /// Device of type, which is unknown for the library (e.g. registered in DeviceRegistry)
+    Other,
+}
+
+impl fmt::Display for DeviceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DeviceKind::Socket => "Socket",
+            DeviceKind::Thermometer => "Thermometer",
+            DeviceKind::PowerStrip => "PowerStrip",
+            DeviceKind::Other => "Other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Parse kind of the device from its name, case is ignored
+///
+/// Example:
+/// ```
+/// use crate::home::devices::DeviceKind;
+///
+/// let kind: DeviceKind = "Socket".parse().unwrap();
+///
+/// # assert_eq!(kind, DeviceKind::Socket);
+/// # assert_eq!("THERMOMETER".parse::<DeviceKind>(), Ok(DeviceKind::Thermometer));
+/// # assert!("lamp".parse::<DeviceKind>().is_err());
+/// ```
+impl FromStr for DeviceKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "socket" => Ok(DeviceKind::Socket),
+            "thermometer" => Ok(DeviceKind::Thermometer),
+            "powerstrip" => Ok(DeviceKind::PowerStrip),
+            _ => Err(format!("Unknown device kind [{}]", kind)),
+        }
+    }
+}
+
 /// General trait for all Devices
 ///
 /// Trait must be to implement only three things in a String form:
 /// - get name of device
 /// - get description of device
 /// - get status of device
-pub trait Device {
+///
+/// Also device must allow to change its name and return its kind.
+/// Devices must be Send + Sync, so Home can be shared between threads
+pub trait Device: Send + Sync {
     fn name(&self) -> &str;
+    /// Method change name of the device, it is called only by the devices store
+    fn set_name(&mut self, name: &str, token: RenameToken);
     fn status(&self) -> String;
     fn info(&self) -> String;
+    fn kind(&self) -> DeviceKind;
+
+    /// Method return boxed copy of the device
+    fn clone_box(&self) -> Box<dyn Device>;
+
+    /// Method return true, if device is Connectable
+    fn is_connectable(&self) -> bool {
+        false
+    }
+
+    /// Method return device as Measurable, if device can return values
+    fn as_measurable(&self) -> Option<&dyn Measurable> {
+        None
+    }
+
+    /// Method return device as Socket, if device is Socket
+    fn as_socket(&self) -> Option<&Socket> {
+        None
+    }
+
+    /// Method return device as mutable Socket, if device is Socket
+    fn as_socket_mut(&mut self) -> Option<&mut Socket> {
+        None
+    }
+
+    /// Method return device as PowerStrip, if device is PowerStrip
+    fn as_power_strip(&self) -> Option<&PowerStrip> {
+        None
+    }
+}
+
+/// Permission to call `Device::set_name`
+///
+/// Token can be created only inside the library, so devices are renamed only through
+/// `Home::rename_device` and names in devices store and links store stay the same
+pub struct RenameToken(());
+
+impl RenameToken {
+    pub(crate) fn new() -> Self {
+        RenameToken(())
+    }
+}
+
+/// Boxed devices are cloned deeply through `Device::clone_box`
+///
+/// Example:
+/// ```
+/// use crate::home::devices::Device;
+/// use crate::home::devices::socket::Socket;
+///
+/// let device: Box<dyn Device> = Box::new(Socket::from("S01", "description of S01", 1000.0));
+/// let mut copy = device.clone();
+/// copy.as_socket_mut().unwrap().power_on();
+///
+/// # assert!(!device.as_socket().unwrap().is_enabled());
+/// # assert!(copy.as_socket().unwrap().is_enabled());
+/// ```
+impl Clone for Box<dyn Device> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
 }
 
 /// Marker trait to declare devices which are connectable to another
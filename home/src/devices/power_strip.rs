@@ -0,0 +1,220 @@
+use super::socket::Socket;
+use crate::devices::{Connectable, Device, DeviceKind, RenameToken};
+
+/// Name of PowerStrip created with default parameters
+pub const DEFAULT_NAME: &str = "default_strip";
+
+/// Power strip of smart home
+///
+/// Power strip contains several sockets, which are managed as one device
+///
+/// Example:
+/// ```
+/// use crate::home::devices::power_strip::PowerStrip;
+/// use crate::home::devices::socket::Socket;
+///
+/// let mut strip = PowerStrip::from("P01", "description of P01");
+/// strip.add_socket(Socket::from("S01", "description of S01", 1000.0)).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct PowerStrip {
+    name: String,
+    description: String,
+    sockets: Vec<Socket>,
+}
+
+impl Default for PowerStrip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerStrip {
+    /// Function create PowerStrip with default parameters and without sockets
+    /// name - default_strip
+    /// description - default description
+    ///
+    /// Example
+    /// ```
+    /// use crate::home::devices::power_strip::PowerStrip;
+    ///
+    /// let strip = PowerStrip::new();
+    /// ```
+    pub fn new() -> Self {
+        PowerStrip::from(DEFAULT_NAME, "default description")
+    }
+
+    /// Function create PowerStrip without sockets
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::power_strip::PowerStrip;
+    ///
+    /// let strip = PowerStrip::from("P01", "description of P01");
+    /// # assert!(strip.sockets().is_empty());
+    /// ```
+    pub fn from(name: &str, description: &str) -> Self {
+        PowerStrip {
+            name: String::from(name),
+            description: String::from(description),
+            sockets: vec![],
+        }
+    }
+
+    /// Method add socket to the strip
+    ///
+    /// If strip already contains socket with same name - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::power_strip::PowerStrip;
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut strip = PowerStrip::from("P01", "description of P01");
+    /// strip.add_socket(Socket::from("S01", "description of S01", 1000.0)).unwrap();
+    ///
+    /// # assert!(strip.add_socket(Socket::from("S01", "description of S01", 1000.0)).is_err());
+    /// ```
+    pub fn add_socket(&mut self, socket: Socket) -> Result<(), String> {
+        if self.socket(socket.name()).is_some() {
+            let message = format!(
+                "Power strip [{}] already contains socket [{}]",
+                self.name,
+                socket.name()
+            );
+            return Err(message);
+        }
+
+        self.sockets.push(socket);
+        Ok(())
+    }
+
+    /// Method remove socket from the strip and return it
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::power_strip::PowerStrip;
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut strip = PowerStrip::from("P01", "description of P01");
+    /// strip.add_socket(Socket::from("S01", "description of S01", 1000.0)).unwrap();
+    ///
+    /// let socket = strip.remove_socket("S01").unwrap();
+    ///
+    /// # assert!(strip.sockets().is_empty());
+    /// # assert!(strip.remove_socket("S01").is_err());
+    /// ```
+    pub fn remove_socket(&mut self, socket_name: &str) -> Result<Socket, String> {
+        match self.sockets.iter().position(|socket| socket.name() == socket_name) {
+            Some(index) => Ok(self.sockets.remove(index)),
+            None => {
+                let message = format!(
+                    "Power strip [{}] not contains socket [{}]",
+                    self.name, socket_name
+                );
+                Err(message)
+            }
+        }
+    }
+
+    /// Method return sockets of the strip in order of adding
+    pub fn sockets(&self) -> &[Socket] {
+        self.sockets.as_slice()
+    }
+
+    /// Method return socket of the strip by name
+    pub fn socket(&self, socket_name: &str) -> Option<&Socket> {
+        self.sockets.iter().find(|socket| socket.name() == socket_name)
+    }
+
+    /// Method return mutable socket of the strip by name
+    pub fn socket_mut(&mut self, socket_name: &str) -> Option<&mut Socket> {
+        self.sockets.iter_mut().find(|socket| socket.name() == socket_name)
+    }
+
+    /// Method return summary power consumption of enabled sockets
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::power_strip::PowerStrip;
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of S01", 1000.0);
+    /// socket.set_power_consumption(300.0).unwrap();
+    /// socket.power_on();
+    /// let mut strip = PowerStrip::from("P01", "description of P01");
+    /// strip.add_socket(socket).unwrap();
+    ///
+    /// # assert_eq!(strip.power_consumption(), 300.0);
+    /// ```
+    pub fn power_consumption(&self) -> f32 {
+        self.sockets
+            .iter()
+            .filter(|socket| socket.is_enabled())
+            .map(|socket| socket.power_consumption())
+            .sum()
+    }
+}
+
+impl Device for PowerStrip {
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn set_name(&mut self, name: &str, _token: RenameToken) {
+        self.name = String::from(name);
+    }
+
+    /// Method return status of PowerStrip
+    ///
+    /// If any socket is overloaded - return OVERLOADED
+    /// If not - return OK
+    fn status(&self) -> String {
+        if self.sockets.iter().any(|socket| socket.is_overloaded()) {
+            String::from("OVERLOADED")
+        } else {
+            String::from("OK")
+        }
+    }
+
+    /// Method generate info about PowerStrip with info of all its sockets
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::Device;
+    /// use crate::home::devices::power_strip::PowerStrip;
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut strip = PowerStrip::from("P01", "description of P01");
+    /// strip.add_socket(Socket::from("S01", "description of S01", 1000.0)).unwrap();
+    /// println!("{}", strip.info());
+    /// ```
+    fn info(&self) -> String {
+        let sockets: Vec<String> = self.sockets.iter().map(|socket| socket.info()).collect();
+        format!(
+            "[POWER STRIP] {}\n{}\nParameters: [\n\tPower consumption = {}\n]\nSockets:\n{}",
+            self.name,
+            self.description,
+            self.power_consumption(),
+            sockets.join("\n")
+        )
+    }
+
+    fn kind(&self) -> DeviceKind {
+        DeviceKind::PowerStrip
+    }
+
+    fn clone_box(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+
+    fn is_connectable(&self) -> bool {
+        true
+    }
+
+    fn as_power_strip(&self) -> Option<&PowerStrip> {
+        Some(self)
+    }
+}
+
+impl Connectable for PowerStrip {}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use super::power_strip::PowerStrip;
+use super::socket::{self, Socket};
+use super::thermometer::Thermometer;
+use super::Device;
+
+/// Constructor of device by its name and description
+pub type DeviceConstructor = fn(&str, &str) -> Box<dyn Device>;
+
+/// Registry of device types
+///
+/// Maps type tag to constructor of device.
+/// Registry created by `new` already contains "socket", "thermometer" and "powerstrip"
+///
+/// Example:
+/// ```
+/// use crate::home::devices::registry::DeviceRegistry;
+///
+/// let registry = DeviceRegistry::new();
+/// let device = registry.build("socket", "S01", "Description of S01").unwrap();
+///
+/// # assert_eq!(device.name(), "S01");
+/// # assert!(registry.contains("powerstrip"));
+/// # assert!(registry.build("unknown", "U01", "Description of U01").is_err());
+/// ```
+pub struct DeviceRegistry {
+    constructors: HashMap<String, DeviceConstructor>,
+}
+
+impl Default for DeviceRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceRegistry {
+    /// Method create new registry with "socket", "thermometer" and "powerstrip" types
+    ///
+    /// Socket is created with default max power, power strip is created without sockets
+    pub fn new() -> Self {
+        let mut registry = DeviceRegistry {
+            constructors: HashMap::new(),
+        };
+        registry.register("socket", |name, description| {
+            Box::new(Socket::from(name, description, socket::DEFAULT_POWER_MAX))
+        });
+        registry.register("thermometer", |name, description| {
+            Box::new(Thermometer::from(name, description))
+        });
+        registry.register("powerstrip", |name, description| {
+            Box::new(PowerStrip::from(name, description))
+        });
+
+        registry
+    }
+
+    /// Method register constructor of device type
+    ///
+    /// If type tag already registered - constructor is replaced
+    pub fn register(&mut self, tag: &str, constructor: DeviceConstructor) {
+        self.constructors.insert(String::from(tag), constructor);
+    }
+
+    /// Method check registered type tag
+    pub fn contains(&self, tag: &str) -> bool {
+        self.constructors.contains_key(tag)
+    }
+
+    /// Method return all registered type tags
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.constructors.keys().cloned().collect();
+        tags.sort();
+        tags
+    }
+
+    /// Method build device of specific type
+    ///
+    /// If type tag registered - return Ok with device
+    /// If not - return Error
+    pub fn build(&self, tag: &str, name: &str, description: &str) -> Result<Box<dyn Device>, String> {
+        match self.constructors.get(tag) {
+            Some(constructor) => Ok(constructor(name, description)),
+            None => {
+                let message = format!("Device type [{}] not registered", tag);
+                Err(message)
+            }
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use super::Device;
+use super::{Device, DeviceKind, RenameToken};
+
+/// Name of Socket created with default parameters
+pub const DEFAULT_NAME: &str = "default";
+
+/// Max power of Socket created with default parameters
+pub const DEFAULT_POWER_MAX: f32 = 100.0;
 
 /// Socket of smart home
 ///
@@ -12,12 +18,15 @@ use super::Device;
 /// // create socket with custom parameters
 /// let socket2 = Socket::from("S01", "description of SW01", 1000.0);
 /// ```
+#[derive(Clone)]
 pub struct Socket {
     name: String,
     description: String,
     power_max: f32,
     power_consumption: f32,
     enabled: bool,
+    firmware_version: Option<String>,
+    voltage: Option<f32>,
 }
 
 impl Default for Socket {
@@ -30,7 +39,7 @@ impl Socket {
     /// Function create Socket with default parameters
     /// name - default
     /// description - default description
-    /// power_max - 100.0
+    /// power_max - DEFAULT_POWER_MAX
     ///
     /// Example
     /// ```
@@ -40,11 +49,13 @@ impl Socket {
     /// ```
     pub fn new() -> Self {
         Socket {
-            name: String::from("default"),
+            name: String::from(DEFAULT_NAME),
             description: String::from("default description"),
-            power_max: 100.0,
+            power_max: DEFAULT_POWER_MAX,
             power_consumption: 0.0,
             enabled: false,
+            firmware_version: None,
+            voltage: None,
         }
     }
 
@@ -63,6 +74,8 @@ impl Socket {
             power_max,
             power_consumption: 0.0,
             enabled: false,
+            firmware_version: None,
+            voltage: None,
         }
     }
 
@@ -91,6 +104,179 @@ impl Socket {
     pub fn _power_off(&mut self) {
         self.enabled = false;
     }
+
+    /// Method return max power of Socket
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// # assert_eq!(socket.power_max(), 1000.0);
+    /// ```
+    pub fn power_max(&self) -> f32 {
+        self.power_max
+    }
+
+    /// Method set max power of Socket
+    ///
+    /// New max power must be positive and finite.
+    /// If current power consumption exceeds new max power - consumption is clamped to it
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.set_power_consumption(800.0).unwrap();
+    /// socket.set_power_max(500.0).unwrap();
+    ///
+    /// # assert_eq!(socket.power_max(), 500.0);
+    /// # assert_eq!(socket.power_consumption(), 500.0);
+    /// # assert!(socket.set_power_max(-1.0).is_err());
+    /// ```
+    pub fn set_power_max(&mut self, watts: f32) -> Result<(), String> {
+        if !watts.is_finite() || watts <= 0.0 {
+            let message = format!("Max power of socket [{}] must be positive, got {}", self.name, watts);
+            return Err(message);
+        }
+
+        self.power_max = watts;
+        if self.power_consumption > watts {
+            self.power_consumption = watts;
+        }
+        Ok(())
+    }
+
+    /// Method set current power consumption of Socket
+    ///
+    /// Consumption must be finite, not negative and not greater than max power
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.set_power_consumption(800.0).unwrap();
+    ///
+    /// # assert_eq!(socket.power_consumption(), 800.0);
+    /// # assert!(socket.set_power_consumption(1200.0).is_err());
+    /// ```
+    pub fn set_power_consumption(&mut self, watts: f32) -> Result<(), String> {
+        if !watts.is_finite() || watts < 0.0 || watts > self.power_max {
+            let message = format!(
+                "Power consumption of socket [{}] must be in range [0, {}], got {}",
+                self.name, self.power_max, watts
+            );
+            return Err(message);
+        }
+
+        self.power_consumption = watts;
+        Ok(())
+    }
+
+    /// Method return current power consumption of Socket
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// # assert_eq!(socket.power_consumption(), 0.0);
+    /// ```
+    pub fn power_consumption(&self) -> f32 {
+        self.power_consumption
+    }
+
+    /// Method return power state of Socket
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.power_on();
+    /// # assert!(socket.is_enabled());
+    /// ```
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Method return true, if power consumption of Socket exceeds its max power
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.set_power_consumption(1000.0).unwrap();
+    /// # assert!(!socket.is_overloaded());
+    /// ```
+    pub fn is_overloaded(&self) -> bool {
+        self.power_consumption > self.power_max
+    }
+
+    /// Method return firmware version of Socket, if it is known
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// # assert_eq!(socket.firmware_version(), None);
+    /// ```
+    pub fn firmware_version(&self) -> Option<&str> {
+        self.firmware_version.as_deref()
+    }
+
+    /// Method set firmware version of Socket, None makes version unknown
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.set_firmware_version(Some("1.2.0"));
+    ///
+    /// # assert_eq!(socket.firmware_version(), Some("1.2.0"));
+    /// ```
+    pub fn set_firmware_version(&mut self, version: Option<&str>) {
+        self.firmware_version = version.map(String::from);
+    }
+
+    /// Method return voltage of Socket in volts, if it is tracked
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// # assert_eq!(socket.voltage(), None);
+    /// ```
+    pub fn voltage(&self) -> Option<f32> {
+        self.voltage
+    }
+
+    /// Method set voltage of Socket in volts, None stops tracking of voltage
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "description of SW01", 1000.0);
+    /// socket.set_voltage(Some(230.0));
+    ///
+    /// # assert_eq!(socket.voltage(), Some(230.0));
+    /// ```
+    pub fn set_voltage(&mut self, volts: Option<f32>) {
+        self.voltage = volts;
+    }
+
+    /// Test hook: set power consumption without checking max power
+    #[cfg(test)]
+    pub(crate) fn set_power_consumption_unchecked(&mut self, watts: f32) {
+        self.power_consumption = watts;
+    }
 }
 
 impl Device for Socket {
@@ -98,12 +284,26 @@ impl Device for Socket {
         self.name.as_str()
     }
 
+    fn set_name(&mut self, name: &str, _token: RenameToken) {
+        self.name = String::from(name);
+    }
+
+    /// Method return status of Socket
+    ///
+    /// If power consumption exceeds max power - return OVERLOADED
+    /// If not - return OK
     fn status(&self) -> String {
-        String::from("asd")
+        if self.is_overloaded() {
+            String::from("OVERLOADED")
+        } else {
+            String::from("OK")
+        }
     }
 
     /// Method generate info about Socket
     ///
+    /// Firmware version and voltage are included in parameters, if they are known
+    ///
     /// Example:
     /// ```
     /// use crate::home::devices::Device;
@@ -113,9 +313,39 @@ impl Device for Socket {
     /// println!("{}", socket.info());
     /// ```
     fn info(&self) -> String {
+        let firmware = match &self.firmware_version {
+            Some(version) => format!("\tFirmware version = {}\n", version),
+            None => String::new(),
+        };
+        let voltage = match self.voltage {
+            Some(volts) => format!("\tVoltage = {}\n", volts),
+            None => String::new(),
+        };
         format!(
-            "[SOCKET] {} [power is on={}]\n{}\nParameters: [\n\tMax power = {}\n\tPower consumption = {}\n]",
-            self.name, self.enabled, self.description, self.power_max, self.power_consumption
+            "[SOCKET] {} [power is on={}]\n{}\nParameters: [\n\tMax power = {}\n\tPower consumption = {}\n{}{}]",
+            self.name,
+            self.enabled,
+            self.description,
+            self.power_max,
+            self.power_consumption,
+            firmware,
+            voltage
         )
     }
+
+    fn kind(&self) -> DeviceKind {
+        DeviceKind::Socket
+    }
+
+    fn clone_box(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+
+    fn as_socket(&self) -> Option<&Socket> {
+        Some(self)
+    }
+
+    fn as_socket_mut(&mut self) -> Option<&mut Socket> {
+        Some(self)
+    }
 }
@@ -1,5 +1,13 @@
+use std::collections::VecDeque;
+
 use super::Measurable;
-use crate::devices::{Connectable, Device};
+use crate::devices::{Connectable, Device, DeviceKind, RenameToken};
+
+/// Name of Thermometer created with default parameters
+pub const DEFAULT_NAME: &str = "default_name";
+
+/// Default count of readings, which are stored in history of Thermometer
+pub const DEFAULT_HISTORY_SIZE: usize = 10;
 
 /// Thermometer is a device of smart home
 ///
@@ -15,10 +23,13 @@ use crate::devices::{Connectable, Device};
 /// // create thermometer with custom parameters
 /// let socket2 = Thermometer::from("T01", "description of T01");
 /// ```
+#[derive(Clone)]
 pub struct Thermometer {
     name: String,
     description: String,
     current_temperature: f32,
+    history: VecDeque<f32>,
+    history_size: usize,
 }
 
 impl Default for Thermometer {
@@ -40,9 +51,11 @@ impl Thermometer {
     /// ```
     pub fn new() -> Self {
         Thermometer {
-            name: String::from("default_name"),
+            name: String::from(DEFAULT_NAME),
             description: String::from("default_description"),
             current_temperature: 0.0,
+            history: VecDeque::with_capacity(DEFAULT_HISTORY_SIZE),
+            history_size: DEFAULT_HISTORY_SIZE,
         }
     }
 
@@ -59,9 +72,115 @@ impl Thermometer {
             name: String::from(name),
             description: String::from(description),
             current_temperature: 0.0,
+            history: VecDeque::with_capacity(DEFAULT_HISTORY_SIZE),
+            history_size: DEFAULT_HISTORY_SIZE,
+        }
+    }
+
+    /// Method set current temperature and save it to history
+    ///
+    /// If history is full, the oldest reading is removed
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let mut term = Thermometer::from("T01", "Description of T01");
+    /// term.set_temperature(21.5);
+    ///
+    /// # assert_eq!(term.temperature(), 21.5);
+    /// # assert_eq!(term.history().len(), 1);
+    /// ```
+    pub fn set_temperature(&mut self, temperature: f32) {
+        self.current_temperature = temperature;
+        if self.history_size == 0 {
+            return;
+        }
+        if self.history.len() == self.history_size {
+            self.history.pop_front();
+        }
+        self.history.push_back(temperature);
+    }
+
+    /// Method return current temperature
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let term = Thermometer::from("T01", "Description of T01");
+    /// # assert_eq!(term.temperature(), 0.0);
+    /// ```
+    pub fn temperature(&self) -> f32 {
+        self.current_temperature
+    }
+
+    /// Method set count of readings, which are stored in history
+    ///
+    /// Size 0 turns history off. If history is longer, the oldest readings are removed
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::{Thermometer, DEFAULT_HISTORY_SIZE};
+    ///
+    /// let mut term = Thermometer::from("T01", "Description of T01");
+    /// # assert_eq!(term.history_size(), DEFAULT_HISTORY_SIZE);
+    ///
+    /// term.set_history_size(3);
+    /// # assert_eq!(term.history_size(), 3);
+    /// ```
+    pub fn set_history_size(&mut self, history_size: usize) {
+        self.history_size = history_size;
+        while self.history.len() > history_size {
+            self.history.pop_front();
         }
     }
 
+    /// Method return count of readings, which are stored in history
+    pub fn history_size(&self) -> usize {
+        self.history_size
+    }
+
+    /// Method return recent readings, from the oldest to the newest
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let mut term = Thermometer::from("T01", "Description of T01");
+    /// term.set_temperature(20.0);
+    /// term.set_temperature(22.0);
+    ///
+    /// # assert_eq!(term.history(), &[20.0, 22.0]);
+    /// ```
+    pub fn history(&self) -> &VecDeque<f32> {
+        &self.history
+    }
+
+    /// Method return average of readings in history
+    ///
+    /// If history is empty - return None
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let mut term = Thermometer::from("T01", "Description of T01");
+    /// # assert_eq!(term.average(), None);
+    ///
+    /// term.set_temperature(20.0);
+    /// term.set_temperature(22.0);
+    /// # assert_eq!(term.average(), Some(21.0));
+    /// ```
+    pub fn average(&self) -> Option<f32> {
+        if self.history.is_empty() {
+            return None;
+        }
+
+        let sum: f32 = self.history.iter().sum();
+        Some(sum / self.history.len() as f32)
+    }
+
     /// Method generate info about Thermometer
     ///
     /// Example:
@@ -84,6 +203,10 @@ impl Device for Thermometer {
         self.name.as_str()
     }
 
+    fn set_name(&mut self, name: &str, _token: RenameToken) {
+        self.name = String::from(name);
+    }
+
     fn status(&self) -> String {
         String::from("OK")
     }
@@ -91,6 +214,22 @@ impl Device for Thermometer {
     fn info(&self) -> String {
         format!("[THERMOMETER] {} \n NO PARAMETERS", self.name)
     }
+
+    fn kind(&self) -> DeviceKind {
+        DeviceKind::Thermometer
+    }
+
+    fn clone_box(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+
+    fn is_connectable(&self) -> bool {
+        true
+    }
+
+    fn as_measurable(&self) -> Option<&dyn Measurable> {
+        Some(self)
+    }
 }
 
 impl Measurable for Thermometer {
@@ -6,15 +6,26 @@ pub mod stores;
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use crate::devices::power_strip::PowerStrip;
+    use crate::devices::registry::DeviceRegistry;
     use crate::devices::thermometer::Thermometer;
+    use crate::devices::{Device, DeviceKind, RenameToken};
+    use crate::services::{
+        Inconsistency, JsonFormat, SchemaFormat, SchemaNode, SchemaStats, DEFAULT_NAME_TAKEN,
+        ServiceDeviceManagement,
+    };
+    use crate::stores::{DeviceId, LinkKind, StoreDeviceLinks, StoreDevices, HOME_NAME};
     use crate::{
         devices::socket::Socket,
-        places::{Home, Room},
+        places::{DeviceSnapshot, Home, Room},
     };
 
     #[test]
     fn add_rooms_work() {
-        let mut home = Home::new("My little Home");
+        let home = Home::new("My little Home");
 
         let r_hallway = Room::new("Hallway");
         let r_living_room = Room::new("Living Room");
@@ -27,7 +38,7 @@ mod tests {
 
     #[test]
     fn add_device_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
         let room_name_01 = "R01";
         let room_01 = Room::new(room_name_01);
         home.add_room(room_01).unwrap();
@@ -40,7 +51,7 @@ mod tests {
 
     #[test]
     fn connect_device_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         let room_name_01 = "R01";
         let room01 = Room::new(room_name_01);
@@ -63,7 +74,7 @@ mod tests {
 
     #[test]
     fn report_home_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         let room_name_01 = "R01";
         let room01 = Room::new(room_name_01);
@@ -88,7 +99,7 @@ mod tests {
 
     #[test]
     fn schema_home_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         let room_name_01 = "R01";
         let room01 = Room::new(room_name_01);
@@ -111,7 +122,7 @@ mod tests {
 
     #[test]
     fn found_device_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         let room_name_01 = "R01";
         let room01 = Room::new(room_name_01);
@@ -137,12 +148,12 @@ mod tests {
         match home.device("S01") {
             Some(_) => println!("test work!"),
             None => panic!("The device contains in the home"),
-        }
+        };
     }
 
     #[test]
     fn remove_room_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         home.add_room(Room::new("R01")).unwrap();
         home.add_room(Room::new("R02")).unwrap();
@@ -159,7 +170,7 @@ mod tests {
 
     #[test]
     fn remove_device_work() {
-        let mut home = Home::new("home");
+        let home = Home::new("home");
 
         let room_name = "R01";
         home.add_room(Room::new(room_name)).unwrap();
@@ -176,4 +187,1980 @@ mod tests {
         assert_eq!(devices.len(), 3);
         assert!(!devices.contains(&String::from(remove_device_name)))
     }
+
+    #[test]
+    fn summary_stats_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+
+        let mut s01 = Socket::from("S01", "Description S01", 1500.0);
+        s01.set_power_consumption(300.0).unwrap();
+        s01.power_on();
+        let mut s02 = Socket::from("S02", "Description S02", 1200.0);
+        s02.set_power_consumption(500.0).unwrap();
+        let t01 = Thermometer::from("T01", "Description of T01");
+        let mut s03 = Socket::from("S03", "Description S03", 1000.0);
+        s03.set_power_consumption(50.0).unwrap();
+        s03.power_on();
+        let mut strip = PowerStrip::from("P01", "Description of P01");
+        strip.add_socket(s03).unwrap();
+        strip.add_socket(Socket::from("S04", "Description S04", 1000.0)).unwrap();
+
+        home.add_device("R01", Box::new(s01)).unwrap();
+        home.add_device("R02", Box::new(s02)).unwrap();
+        home.add_device("R02", Box::new(t01)).unwrap();
+        home.add_device("R02", Box::new(strip)).unwrap();
+
+        let stats = home.summary_stats();
+
+        assert_eq!(stats.rooms, 2);
+        assert_eq!(stats.devices, 4);
+        assert_eq!(stats.sockets, 4);
+        assert_eq!(stats.thermometers, 1);
+        assert_eq!(stats.total_power, 350.0);
+        assert_eq!(stats.enabled_sockets, 2);
+        let report_power: f32 = home.power_report().iter().map(|(_, power)| power).sum();
+        assert_eq!(stats.total_power, report_power);
+    }
+
+    #[test]
+    fn links_kinds_work() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+        store.connect_device("S01", "T01");
+
+        let room_links = store.links("R01");
+        let socket_links = store.links("S01");
+
+        assert_eq!(
+            room_links,
+            vec![(LinkKind::Membership, "S01"), (LinkKind::Membership, "T01")]
+        );
+        assert_eq!(socket_links, vec![(LinkKind::Connection, "T01")]);
+    }
+
+    #[test]
+    fn remove_connected_device_work() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+        store.connect_device("S01", "T01");
+
+        store.remove_device("T01").unwrap();
+
+        assert!(store.links("R01").iter().all(|(_, name)| *name != "T01"));
+        assert!(store.links("S01").is_empty());
+    }
+
+    #[test]
+    fn device_path_work() {
+        let home = Home::new("home");
+        let room_name = "Kitchen";
+        home.add_room(Room::new(room_name)).unwrap();
+
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        home.connect_device(room_name, "S01", "T01").unwrap();
+        home.connect_device(room_name, "T01", "T02").unwrap();
+
+        assert_eq!(home.device_path("T02").unwrap(), vec!["Kitchen", "S01", "T01", "T02"]);
+        assert_eq!(home.device_path("S01").unwrap(), vec!["Kitchen", "S01"]);
+        assert!(home.device_path("T03").is_none());
+    }
+
+    #[test]
+    fn validate_work() {
+        let mut home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        assert!(home.validate().is_empty());
+
+        home.manager_devices_mut()
+            .add_device_to_store_only(Box::new(Socket::from("S02", "S02 description", 1000.0)));
+        home.manager_devices_mut()
+            .add_device_to_schema_only(room_name, "S03");
+
+        let inconsistencies = home.validate();
+
+        assert_eq!(inconsistencies.len(), 3);
+        assert!(inconsistencies.contains(&Inconsistency::OrphanedDevice(String::from("S02"))));
+        assert!(inconsistencies.contains(&Inconsistency::DanglingLink(String::from("S03"))));
+        assert!(inconsistencies.contains(&Inconsistency::MissingRoomDevice {
+            room: String::from(room_name),
+            device: String::from("S03"),
+        }));
+    }
+
+    #[test]
+    fn iterate_home_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        let mut count = 0;
+        for device in &home {
+            assert_eq!(home.device_snapshot(device.name.as_str()), Some(device));
+            count += 1;
+        }
+
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn reparent_device_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device(room_name, "S01", "T01").unwrap();
+
+        home.reparent_device("T01", "S02").unwrap();
+
+        assert_eq!(home.device_path("T01").unwrap(), vec!["R01", "S02", "T01"]);
+    }
+
+    #[test]
+    fn reparent_device_cycle_fails() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device(room_name, "S01", "T01").unwrap();
+        home.connect_device(room_name, "T01", "T02").unwrap();
+
+        let result = home.reparent_device("T01", "T02");
+
+        assert!(result.is_err());
+        assert_eq!(home.device_path("T02").unwrap(), vec!["R01", "S01", "T01", "T02"]);
+    }
+
+    #[derive(Clone)]
+    struct Lamp {
+        name: String,
+    }
+
+    impl Device for Lamp {
+        fn name(&self) -> &str {
+            self.name.as_str()
+        }
+
+        fn set_name(&mut self, name: &str, _token: RenameToken) {
+            self.name = String::from(name);
+        }
+
+        fn status(&self) -> String {
+            String::from("OK")
+        }
+
+        fn info(&self) -> String {
+            format!("[LAMP] {}", self.name)
+        }
+
+        fn kind(&self) -> DeviceKind {
+            DeviceKind::Other
+        }
+
+        fn clone_box(&self) -> Box<dyn Device> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn registry_custom_device_work() {
+        let mut registry = DeviceRegistry::new();
+        registry.register("lamp", |name, _| Box::new(Lamp { name: String::from(name) }));
+
+        let lamp = registry.build("lamp", "L01", "Description of L01").unwrap();
+        let socket = registry.build("socket", "S01", "Description of S01").unwrap();
+        let strip = registry.build("powerstrip", "P01", "Description of P01").unwrap();
+
+        assert_eq!(lamp.info(), "[LAMP] L01");
+        assert_eq!(lamp.kind(), DeviceKind::Other);
+        assert_eq!(socket.kind(), DeviceKind::Socket);
+        assert_eq!(socket.as_socket().unwrap().power_max(), Socket::new().power_max());
+        assert_eq!(strip.kind(), DeviceKind::PowerStrip);
+        assert!(strip.as_power_strip().unwrap().sockets().is_empty());
+        assert_eq!(registry.tags(), vec!["lamp", "powerstrip", "socket", "thermometer"]);
+        for tag in registry.tags() {
+            assert!(tag == "lamp" || tag.parse::<DeviceKind>().is_ok());
+        }
+    }
+
+    #[test]
+    fn remove_devices_work() {
+        let home = Home::new("home");
+
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S03", "S03 description", 1000.0))).unwrap();
+
+        let result = home.remove_devices(&["S01", "S02"]);
+
+        assert!(result.is_ok());
+        assert_eq!(home.devices(room_name), vec!["S03"]);
+    }
+
+    #[test]
+    fn remove_devices_partially_found() {
+        let home = Home::new("home");
+
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+
+        let result = home.remove_devices(&["S01", "S05", "S02", "S06"]);
+
+        assert_eq!(result.unwrap_err(), "Devices with names [S05, S06] not found");
+        assert!(home.devices(room_name).is_empty());
+        assert!(home.device("S01").is_none());
+        assert!(home.device("S02").is_none());
+    }
+
+    #[test]
+    fn with_capacity_work() {
+        let home = Home::with_capacity("home", 2, 8);
+        let home_new = Home::new("home");
+
+        for room_index in 0..4 {
+            let room_name = format!("R{:02}", room_index);
+            home.add_room(Room::new(room_name.as_str())).unwrap();
+            home_new.add_room(Room::new(room_name.as_str())).unwrap();
+
+            for device_index in 0..10 {
+                let device_name = format!("S{:02}{:02}", room_index, device_index);
+                let socket = Socket::from(device_name.as_str(), "description", 1000.0);
+                let socket_new = Socket::from(device_name.as_str(), "description", 1000.0);
+                home.add_device(room_name.as_str(), Box::new(socket)).unwrap();
+                home_new.add_device(room_name.as_str(), Box::new(socket_new)).unwrap();
+            }
+        }
+
+        assert_eq!(home.rooms(), home_new.rooms());
+        for room_name in home.rooms() {
+            assert_eq!(home.devices(room_name.as_str()), home_new.devices(room_name.as_str()));
+        }
+        assert_eq!(home.summary_stats().devices, 40);
+    }
+
+    #[test]
+    fn room_devices_recursive_work() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+        store.add_device("R01", "S02").unwrap();
+        // T01 is connected to the socket, but it is placed in the room too
+        store.connect_device("S01", "T01");
+        store.connect_device("S02", "S01");
+        store.connect_device("S01", "S02");
+
+        let devices = store.room_devices_recursive("R01");
+
+        assert_eq!(devices, vec!["S01", "T01", "S02"]);
+    }
+
+    #[test]
+    fn remove_device_cascade_work() {
+        let home = Home::new("home");
+
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device(room_name, "S01", "T01").unwrap();
+        home.connect_device(room_name, "S01", "T02").unwrap();
+
+        let removed = home.remove_device_cascade("S01").unwrap();
+
+        assert_eq!(removed, vec!["S01", "T01", "T02"]);
+        assert_eq!(home.devices(room_name), vec!["S02"]);
+        for device_name in removed {
+            assert!(home.device(device_name.as_str()).is_none());
+        }
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn borrowed_iterators_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+
+        let rooms: Vec<String> = home.with_rooms(|rooms| rooms.map(String::from).collect());
+        let devices: Vec<String> =
+            home.with_devices("R01", |devices| devices.map(String::from).collect());
+
+        assert_eq!(rooms, home.rooms());
+        assert_eq!(devices, home.devices("R01"));
+    }
+
+    #[test]
+    fn add_reserved_name_fails() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+
+        let room_result = home.add_room(Room::new(HOME_NAME));
+        let device_result = home.add_device("R01", Box::new(Socket::from(HOME_NAME, "description", 1000.0)));
+
+        assert_eq!(
+            room_result.unwrap_err(),
+            "Name [HOME] is reserved for the root of the home schema"
+        );
+        assert!(device_result.is_err());
+        assert_eq!(home.rooms(), vec!["R01"]);
+        assert!(home.devices("R01").is_empty());
+        assert!(home.device(HOME_NAME).is_none());
+    }
+
+    #[test]
+    fn custom_root_name_work() {
+        let mut store = StoreDeviceLinks::with_root("ROOT");
+        store.add_room("HOME");
+        store.add_device("HOME", "S01").unwrap();
+
+        assert!(store.is_root("ROOT"));
+        assert_eq!(store.rooms().collect::<Vec<&String>>(), vec!["HOME"]);
+        assert_eq!(store.device_path("S01").unwrap(), vec!["HOME", "S01"]);
+    }
+
+    #[test]
+    fn connectables_policy_off_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+
+        let result = home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description")));
+
+        assert!(!home.require_connection_for_connectables());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn connectables_policy_on_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.set_require_connection_for_connectables(true);
+
+        let socket_result = home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0)));
+        let direct_result = home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description")));
+        let connected_result =
+            home.add_connected_device(room_name, "S01", Box::new(Thermometer::from("T01", "T01 description")));
+
+        assert!(socket_result.is_ok());
+        assert!(direct_result.is_err());
+        assert!(connected_result.is_ok());
+        assert_eq!(home.device_path("T01").unwrap(), vec!["R01", "S01", "T01"]);
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn device_kind_display_work() {
+        assert_eq!(DeviceKind::Socket.to_string(), "Socket");
+        assert_eq!(DeviceKind::Thermometer.to_string(), "Thermometer");
+        assert_eq!(format!("{}", Socket::new().kind()), "Socket");
+    }
+
+    #[test]
+    fn connect_device_max_depth_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.set_max_depth(3);
+
+        for index in 0..5 {
+            let name = format!("T{:02}", index);
+            home.add_device(room_name, Box::new(Thermometer::from(name.as_str(), "description"))).unwrap();
+        }
+        home.connect_device(room_name, "T00", "T01").unwrap();
+        home.connect_device(room_name, "T01", "T02").unwrap();
+        home.connect_device(room_name, "T02", "T03").unwrap();
+
+        let result = home.connect_device(room_name, "T03", "T04");
+
+        assert!(result.is_err());
+        assert_eq!(home.device_path("T03").unwrap(), vec!["R01", "T00", "T01", "T02", "T03"]);
+        assert_eq!(home.device_path("T04").unwrap(), vec!["R01", "T04"]);
+    }
+
+    #[test]
+    fn connect_subtree_max_depth_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.set_max_depth(2);
+
+        for index in 0..4 {
+            let name = format!("T{:02}", index);
+            home.add_device(room_name, Box::new(Thermometer::from(name.as_str(), "description"))).unwrap();
+        }
+        home.connect_device(room_name, "T00", "T01").unwrap();
+        home.connect_device(room_name, "T02", "T03").unwrap();
+
+        // T02 -> T03 chain under T01 makes depth 3
+        assert!(home.connect_device(room_name, "T01", "T02").is_err());
+        assert!(home.connect_device(room_name, "T00", "T02").is_ok());
+    }
+
+    #[test]
+    fn try_devices_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        assert_eq!(home.try_devices("R01"), Ok(vec![]));
+        assert_eq!(home.try_devices("R02"), Ok(vec![String::from("S01")]));
+        assert_eq!(
+            home.try_devices("R03"),
+            Err(String::from("Room with name [R03] not found"))
+        );
+    }
+
+    #[test]
+    fn home_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Home>();
+    }
+
+    #[test]
+    fn shared_home_readers_and_writer_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        let home = Arc::new(home);
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let home = Arc::clone(&home);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        let stats = home.summary_stats();
+                        assert!(stats.devices <= 10);
+                        assert!(home.validate().is_empty());
+                        let all_sockets = home.with_devices("R01", |mut devices| {
+                            devices.all(|name| name.starts_with('S'))
+                        });
+                        assert!(all_sockets);
+                    }
+                })
+            })
+            .collect();
+
+        let writer = {
+            let home = Arc::clone(&home);
+            thread::spawn(move || {
+                for index in 0..10 {
+                    let name = format!("S{:02}", index);
+                    let socket = Socket::from(name.as_str(), "description", 1000.0);
+                    home.add_device("R01", Box::new(socket)).unwrap();
+                }
+                home.set_all_sockets(true);
+            })
+        };
+
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        writer.join().unwrap();
+
+        assert_eq!(home.devices("R01").len(), 10);
+        assert_eq!(home.summary_stats().enabled_sockets, 10);
+        assert!(home.validate().is_empty());
+    }
+
+
+    #[test]
+    fn import_csv_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        let csv = "R01,socket,S01,1500\nR02,Socket,S02,1000\n\nR02,thermometer,T01,\nR03,thermometer,T02";
+
+        let result = home.import_csv(csv);
+
+        assert_eq!(result, Ok(4));
+        assert_eq!(home.rooms(), vec!["R01", "R02", "R03"]);
+        assert_eq!(home.devices("R01"), vec!["S01"]);
+        assert_eq!(home.devices("R02"), vec!["S02", "T01"]);
+        assert_eq!(home.devices("R03"), vec!["T02"]);
+        assert_eq!(home.summary_stats().sockets, 2);
+        let device = home.device("S01").unwrap();
+        assert_eq!(device.as_socket().unwrap().power_max(), 1500.0);
+    }
+
+    #[test]
+    fn import_csv_unknown_kind_fails() {
+        let home = Home::new("home");
+        let csv = "R01,socket,S01,1500\nR01,lamp,L01";
+
+        let result = home.import_csv(csv);
+
+        assert_eq!(result, Err(String::from("Line 2: Unknown device kind [lamp]")));
+        assert!(home.rooms().is_empty());
+        assert!(home.device("S01").is_none());
+    }
+
+    #[test]
+    fn import_csv_rolls_back_on_add_error() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        let csv = "R01,socket,S01,1500\nR02,socket,S02,1000\nR01,socket,S01,1500";
+
+        let result = home.import_csv(csv);
+
+        assert!(result.unwrap_err().starts_with("Line 3: "));
+        assert_eq!(home.rooms(), vec!["R01"]);
+        assert!(home.devices("R01").is_empty());
+        assert_eq!(home.summary_stats().devices, 0);
+    }
+
+    #[test]
+    fn socket_power_max_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        socket.set_power_consumption(800.0).unwrap();
+
+        socket.set_power_max(2000.0).unwrap();
+        assert_eq!(socket.power_max(), 2000.0);
+        assert_eq!(socket.power_consumption(), 800.0);
+
+        socket.set_power_max(500.0).unwrap();
+        assert_eq!(socket.power_max(), 500.0);
+        assert_eq!(socket.power_consumption(), 500.0);
+
+        assert!(socket.set_power_max(0.0).is_err());
+        assert!(socket.set_power_max(f32::NAN).is_err());
+        assert!(socket.set_power_max(f32::INFINITY).is_err());
+        assert_eq!(socket.power_max(), 500.0);
+    }
+
+    #[test]
+    fn store_iter_mut_work() {
+        let mut store = StoreDevices::new();
+        store.add_device(Box::new(Socket::from("S01", "S01 description", 1000.0)));
+        store.add_device(Box::new(Socket::from("S02", "S02 description", 2000.0)));
+        store.add_device(Box::new(Thermometer::from("T01", "T01 description")));
+
+        for device in store.iter_mut() {
+            if let Some(socket) = device.as_socket_mut() {
+                socket.power_on();
+                socket.set_power_consumption(socket.power_max() / 2.0).unwrap();
+            }
+        }
+
+        let sockets: Vec<&Socket> = store.iter().filter_map(|device| device.as_socket()).collect();
+        assert_eq!(sockets.len(), 2);
+        assert!(sockets.iter().all(|socket| socket.is_enabled()));
+        assert_eq!(sockets[0].power_consumption(), 500.0);
+        assert_eq!(sockets[1].power_consumption(), 1000.0);
+    }
+
+    #[test]
+    fn set_all_sockets_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        home.set_all_sockets(true);
+        assert_eq!(home.summary_stats().enabled_sockets, 2);
+
+        home.set_all_sockets(false);
+        assert_eq!(home.summary_stats().enabled_sockets, 0);
+    }
+
+    #[test]
+    fn rename_device_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device(room_name, "S01", "T02").unwrap();
+        home.connect_device(room_name, "T02", "T01").unwrap();
+
+        home.rename_device("T02", "T99").unwrap();
+
+        assert_eq!(home.devices(room_name), vec!["S01", "T99", "T01"]);
+        assert_eq!(home.device_path("T01").unwrap(), vec!["R01", "S01", "T99", "T01"]);
+        assert_eq!(home.device("T99").unwrap().name(), "T99");
+        assert!(home.device("T02").is_none());
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn is_empty_work() {
+        let home = Home::new("home");
+        assert!(home.is_empty());
+        assert_eq!(home.room_is_empty("R01"), None);
+
+        home.add_room(Room::new("R01")).unwrap();
+        assert!(!home.is_empty());
+        assert_eq!(home.room_is_empty("R01"), Some(true));
+
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        assert!(!home.is_empty());
+        assert_eq!(home.room_is_empty("R01"), Some(false));
+    }
+
+    #[test]
+    fn connect_device_twice_work() {
+        let home = Home::new("home");
+        let room_name = "R01";
+        home.add_room(Room::new(room_name)).unwrap();
+        home.add_device(room_name, Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        assert_eq!(home.connect_device(room_name, "S01", "T01"), Ok(true));
+        assert_eq!(home.connect_device(room_name, "S01", "T01"), Ok(false));
+        assert_eq!(home.room_devices_recursive(room_name), vec!["S01", "T01"]);
+    }
+
+    #[test]
+    fn remove_room_with_devices_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        let removed = home.remove_room("R01").unwrap();
+
+        assert_eq!(removed, vec!["S01", "T01"]);
+        assert_eq!(home.rooms(), vec!["R02"]);
+        assert!(home.device("S01").is_none());
+        assert!(home.device("T01").is_none());
+        assert!(home.device_path("T01").is_none());
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn schema_json_contains_rooms_and_links() {
+        let home = Home::new("home");
+        home.add_room(Room::new("Kitchen")).unwrap();
+        home.add_room(Room::new("Hall \"main\"")).unwrap();
+        home.add_device("Kitchen", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("Kitchen", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("Kitchen", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device("Kitchen", "S01", "T01").unwrap();
+        home.connect_device("Kitchen", "T01", "T02").unwrap();
+
+        let json = home.schema_json();
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"rooms":[{"name":"Kitchen","devices":["S01","T01","T02"]},"#,
+                r#"{"name":"Hall \"main\"","devices":[]}],"#,
+                r#""links":[{"from":"S01","to":"T01"},{"from":"T01","to":"T02"}]}"#
+            )
+        );
+        assert!(!json.contains("description"));
+    }
+
+    #[test]
+    fn device_count_by_room_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_room(Room::new("R03")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R03", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        assert_eq!(
+            home.device_count_by_room(),
+            vec![
+                (String::from("R01"), 2),
+                (String::from("R02"), 0),
+                (String::from("R03"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn device_children_lists_only_connections() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "S01", "T02").unwrap();
+        home.connect_device("R01", "T02", "T03").unwrap();
+
+        assert_eq!(home.device_children("S01"), vec!["T01", "T02"]);
+        assert_eq!(home.device_children("T02"), vec!["T03"]);
+        assert!(home.device_children("T01").is_empty());
+    }
+
+    #[test]
+    fn report_contains_measurements_section() {
+        let mut service = ServiceDeviceManagement::new();
+        service.add_room(&Room::new("R01")).unwrap();
+        service.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        service.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        service.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        let report = service.collect_report();
+        let (devices, measurements) = report.split_once("Measurements:\n").unwrap();
+
+        assert!(devices.contains("S01"));
+        assert_eq!(measurements, "T01: sample info\nT02: sample info\n");
+    }
+
+    #[test]
+    fn connect_device_not_in_devices_store_fails() {
+        let mut home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.manager_devices_mut().add_device_to_schema_only("R01", "T01");
+
+        assert!(home.connect_device("R01", "S01", "T01").is_err());
+        assert!(home.connect_device("R01", "T01", "S01").is_err());
+        assert!(home.device_children("S01").is_empty());
+        assert!(home.device_children("T01").is_empty());
+    }
+
+    #[test]
+    fn restore_snapshot_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        let memento = home.take_snapshot();
+        home.remove_device("S01").unwrap();
+        home.remove_room("R01").unwrap();
+        assert!(home.rooms().is_empty());
+
+        home.restore_snapshot(memento);
+
+        assert_eq!(home.rooms(), vec!["R01"]);
+        assert_eq!(home.devices("R01"), vec!["S01", "T01"]);
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+        assert_eq!(home.device("S01").unwrap().kind(), DeviceKind::Socket);
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn snapshot_is_independent_of_home() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        let memento = home.take_snapshot();
+        home.set_all_sockets(true);
+        home.rename_device("S01", "S02").unwrap();
+
+        home.restore_snapshot(memento);
+
+        let device = home.device("S01").unwrap();
+        let socket = device.as_socket().unwrap();
+        assert!(!socket.is_enabled());
+        assert!(home.device("S02").is_none());
+    }
+
+    #[test]
+    fn device_kind_from_str_work() {
+        assert_eq!("socket".parse::<DeviceKind>(), Ok(DeviceKind::Socket));
+        assert_eq!("Socket".parse::<DeviceKind>(), Ok(DeviceKind::Socket));
+        assert_eq!("thermometer".parse::<DeviceKind>(), Ok(DeviceKind::Thermometer));
+        assert_eq!("ThermoMeter".parse::<DeviceKind>(), Ok(DeviceKind::Thermometer));
+
+        let error = "lamp".parse::<DeviceKind>().unwrap_err();
+        assert_eq!(error, "Unknown device kind [lamp]");
+    }
+
+    #[test]
+    fn rooms_page_work() {
+        let home = Home::new("home");
+        for room_name in ["R01", "R02", "R03", "R04", "R05"] {
+            home.add_room(Room::new(room_name)).unwrap();
+        }
+
+        assert_eq!(home.rooms_page(1, 2), vec!["R02", "R03"]);
+        assert_eq!(home.rooms_page(3, 10), vec!["R04", "R05"]);
+        assert_eq!(home.rooms_page(0, usize::MAX), home.rooms());
+        assert!(home.rooms_page(5, 1).is_empty());
+        assert!(home.rooms_page(100, 1).is_empty());
+        assert!(home.rooms_page(0, 0).is_empty());
+    }
+
+    #[test]
+    fn add_device_with_same_name_fails() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        let same_room = home.add_device("R01", Box::new(Thermometer::from("S01", "T01 description")));
+        let other_room = home.add_device("R02", Box::new(Thermometer::from("S01", "T01 description")));
+
+        assert!(same_room.is_err());
+        assert!(other_room.is_err());
+        assert_eq!(home.device("S01").unwrap().kind(), DeviceKind::Socket);
+        assert!(home.devices("R02").is_empty());
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn store_contains_name_work() {
+        let mut store = StoreDevices::new();
+        store.add_device(Box::new(Socket::from("S01", "S01 description", 1000.0)));
+
+        assert!(store.contains_name("S01"));
+        assert!(!store.contains_name("T01"));
+    }
+
+    #[test]
+    fn set_home_name_work() {
+        let mut home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+
+        home.set_name("new home");
+
+        assert_eq!(home.name(), "new home");
+        assert_eq!(home.rooms(), vec!["R01"]);
+    }
+
+    #[test]
+    fn connected_devices_keep_connection_order() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        for device_name in ["T03", "T01", "T02"] {
+            home.add_device("R01", Box::new(Thermometer::from(device_name, ""))).unwrap();
+        }
+
+        home.connect_device("R01", "S01", "T02").unwrap();
+        home.connect_device("R01", "S01", "T03").unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        assert_eq!(home.connected_devices_ordered("S01"), vec!["T02", "T03", "T01"]);
+        assert_eq!(home.device_children("S01"), vec!["T02", "T03", "T01"]);
+        assert!(home.schema_json().contains(concat!(
+            r#"{"from":"S01","to":"T02"},"#,
+            r#"{"from":"S01","to":"T03"},"#,
+            r#"{"from":"S01","to":"T01"}"#
+        )));
+    }
+
+    #[test]
+    fn store_members_of_missing_and_populated_room() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+
+        assert_eq!(store.members("Unknown room").count(), 0);
+        assert_eq!(store.members("R01").collect::<Vec<&String>>(), vec!["S01", "T01"]);
+        assert_eq!(store.connections("Unknown device").count(), 0);
+    }
+
+    #[test]
+    fn connect_cross_room_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        assert!(home.connect_device("R01", "S01", "T01").is_err());
+        assert!(home.connect_device("R02", "S01", "T01").is_err());
+        assert!(home.connect_cross_room("R01", "S01", "R01", "T01").is_err());
+
+        assert_eq!(home.connect_cross_room("R01", "S01", "R02", "T01"), Ok(true));
+        assert_eq!(home.connect_cross_room("R01", "S01", "R02", "T01"), Ok(false));
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+        assert_eq!(home.devices("R02"), vec!["T01"]);
+        assert!(home
+            .schema_json()
+            .contains(r#"{"from":"S01","to":"T01","cross_room":true}"#));
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn links_mark_cross_room_connections() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_room("R02");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+        store.add_device("R02", "T02").unwrap();
+        store.connect_device("S01", "T01");
+        store.connect_device("S01", "T02");
+
+        assert_eq!(
+            store.links("S01"),
+            vec![(LinkKind::Connection, "T01"), (LinkKind::CrossRoom, "T02")]
+        );
+    }
+
+    #[test]
+    fn total_and_top_level_devices_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        assert_eq!(home.total_devices(), 0);
+        assert_eq!(home.top_level_devices(), 0);
+
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        assert_eq!(home.total_devices(), 2);
+        assert_eq!(home.top_level_devices(), 1);
+
+        home.add_device("R02", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        assert_eq!(home.total_devices(), 3);
+        assert_eq!(home.top_level_devices(), 2);
+    }
+
+    #[test]
+    fn socket_overload_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        socket.set_power_consumption(1000.0).unwrap();
+        assert!(!socket.is_overloaded());
+        assert_eq!(socket.status(), "OK");
+
+        socket.set_power_consumption_unchecked(1500.0);
+        assert!(socket.is_overloaded());
+        assert_eq!(socket.status(), "OVERLOADED");
+    }
+
+    #[test]
+    fn overloaded_sockets_work() {
+        let mut overloaded = Socket::from("S02", "S02 description", 1000.0);
+        overloaded.set_power_consumption_unchecked(2000.0);
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        assert!(home.overloaded_sockets().is_empty());
+
+        home.add_device("R01", Box::new(overloaded)).unwrap();
+
+        assert_eq!(home.overloaded_sockets(), vec!["S02"]);
+    }
+
+    #[test]
+    fn can_connect_returns_connect_device_errors() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R01", Box::new(Lamp { name: String::from("L01") })).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.connect_device("R01", "T01", "T02").unwrap();
+        let schema_before = home.schema_json();
+
+        let invalid = [
+            ("S01", "Unknown"), // unknown device
+            ("T01", "S01"),     // socket is not connectable
+            ("S01", "L01"),     // lamp is not connectable
+            ("T01", "T01"),     // connection to itself
+            ("T02", "T01"),     // cycle
+            ("S01", "T03"),     // device of another room
+        ];
+        for (parent, device) in invalid {
+            let error = home.can_connect(parent, device).unwrap_err();
+            assert_eq!(home.schema_json(), schema_before);
+            assert_eq!(home.connect_device("R01", parent, device), Err(error));
+        }
+
+        assert_eq!(home.can_connect("S01", "T01"), Ok(()));
+        assert_eq!(home.can_connect("T01", "T02"), Ok(()));
+        assert_eq!(home.schema_json(), schema_before);
+    }
+
+    #[test]
+    fn connect_device_labeled_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        assert_eq!(home.connect_device_labeled("R01", "S01", "T01", "controls"), Ok(true));
+        home.connect_device("R01", "S01", "T02").unwrap();
+
+        assert_eq!(home.connection_label("S01", "T01").as_deref(), Some("controls"));
+        assert_eq!(home.connection_label("S01", "T02"), None);
+        assert!(home.schema_json().contains(concat!(
+            r#"{"from":"S01","to":"T01","label":"controls"},"#,
+            r#"{"from":"S01","to":"T02"}"#
+        )));
+
+        assert_eq!(home.connect_device_labeled("R01", "S01", "T01", "monitors"), Ok(false));
+        assert_eq!(home.connection_label("S01", "T01").as_deref(), Some("monitors"));
+        assert!(home.connect_device_labeled("R01", "T01", "S01", "controls").is_err());
+        assert_eq!(home.connection_label("T01", "S01"), None);
+    }
+
+    #[test]
+    fn connection_label_follows_device_changes() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "controls").unwrap();
+
+        home.rename_device("T01", "T99").unwrap();
+        assert_eq!(home.connection_label("S01", "T99").as_deref(), Some("controls"));
+
+        home.reparent_device("T99", "S02").unwrap();
+        assert_eq!(home.connection_label("S01", "T99"), None);
+        assert_eq!(home.connection_label("S02", "T99"), None);
+
+        home.connect_device_labeled("R01", "S02", "T99", "monitors").unwrap();
+        home.remove_device("T99").unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T99", "T99 description"))).unwrap();
+        home.connect_device("R01", "S02", "T99").unwrap();
+        assert_eq!(home.connection_label("S02", "T99"), None);
+    }
+
+    #[test]
+    fn require_device_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        let device = home.require_device("T01").unwrap();
+        assert_eq!(device.name(), "T01");
+        assert_eq!(device.kind(), DeviceKind::Thermometer);
+
+        let error = home.require_device("T02").map(|device| device.status()).unwrap_err();
+        assert_eq!(error, "Home not contains device[T02]");
+    }
+
+    #[test]
+    fn connect_device_max_children_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.set_max_children(Some(1));
+
+        home.connect_device("R01", "S01", "T01").unwrap();
+        let error = home.connect_device("R01", "S01", "T02").unwrap_err();
+
+        assert!(error.contains("S01"));
+        assert!(error.contains("max 1 children"));
+        assert_eq!(home.connect_device("R01", "S01", "T01"), Ok(false));
+        assert!(home.can_connect("S01", "T02").is_err());
+        assert!(home.reparent_device("T01", "S02").is_ok());
+        assert!(home.reparent_device("T01", "S02").is_ok());
+        assert!(home.connect_device("R01", "S01", "T02").is_ok());
+
+        home.set_max_children(None);
+        home.add_device("R01", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.connect_device("R01", "S01", "T03").unwrap();
+        assert_eq!(home.device_children("S01"), vec!["T02", "T03"]);
+    }
+
+    #[test]
+    fn home_from_devices_work() {
+        let devices: Vec<Box<dyn Device>> = vec![
+            Box::new(Socket::from("S01", "S01 description", 1000.0)),
+            Box::new(Socket::from("S02", "S02 description", 1000.0)),
+            Box::new(Socket::from("S03", "S03 description", 1000.0)),
+        ];
+
+        let home = Home::from_devices("home", "R01", devices).unwrap();
+
+        assert_eq!(home.name(), "home");
+        assert_eq!(home.rooms(), vec!["R01"]);
+        assert_eq!(home.devices("R01"), vec!["S01", "S02", "S03"]);
+        assert_eq!(home.summary_stats().sockets, 3);
+    }
+
+    #[test]
+    fn home_from_devices_with_duplicate_fails() {
+        let devices: Vec<Box<dyn Device>> = vec![
+            Box::new(Socket::from("S01", "S01 description", 1000.0)),
+            Box::new(Thermometer::from("S01", "T01 description")),
+        ];
+
+        assert!(Home::from_devices("home", "R01", devices).is_err());
+    }
+
+    #[test]
+    fn snapshot_keeps_connections() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "powers").unwrap();
+        home.connect_device("R01", "T01", "T02").unwrap();
+        home.connect_cross_room("R01", "S01", "R02", "T03").unwrap();
+        let schema_before = home.schema_json();
+
+        let memento = home.take_snapshot();
+        home.remove_device_cascade("S01").unwrap();
+        home.restore_snapshot(memento);
+
+        assert_eq!(home.schema_json(), schema_before);
+        assert_eq!(home.device_children("S01"), vec!["T01", "T03"]);
+        assert_eq!(home.connected_devices_ordered("S01"), vec!["T01", "T02", "T03"]);
+        assert_eq!(home.connection_label("S01", "T01").as_deref(), Some("powers"));
+        assert_eq!(home.device_path("T02").unwrap(), vec!["R01", "S01", "T01", "T02"]);
+    }
+
+    #[test]
+    fn thermometer_history_work() {
+        let mut thermometer = Thermometer::from("T01", "T01 description");
+        thermometer.set_history_size(3);
+
+        for temperature in [10.0, 20.0, 30.0, 40.0, 50.0] {
+            thermometer.set_temperature(temperature);
+        }
+
+        assert_eq!(thermometer.temperature(), 50.0);
+        assert_eq!(thermometer.history(), &[30.0, 40.0, 50.0]);
+        assert_eq!(thermometer.average(), Some(40.0));
+
+        thermometer.set_history_size(1);
+        assert_eq!(thermometer.history(), &[50.0]);
+
+        thermometer.set_history_size(0);
+        thermometer.set_temperature(60.0);
+        assert!(thermometer.history().is_empty());
+        assert_eq!(thermometer.average(), None);
+        assert_eq!(thermometer.temperature(), 60.0);
+    }
+
+    #[test]
+    fn add_second_default_device_fails_with_hint() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::new())).unwrap();
+        home.add_device("R01", Box::new(Thermometer::new())).unwrap();
+
+        let socket_error = home.add_device("R02", Box::new(Socket::new())).unwrap_err();
+        let thermometer_error = home.add_device("R01", Box::new(Thermometer::new())).unwrap_err();
+        let duplicate_error = home
+            .add_device("R01", Box::new(Socket::from("default_name", "", 1000.0)))
+            .unwrap_err();
+
+        assert!(socket_error.starts_with(DEFAULT_NAME_TAKEN));
+        assert!(socket_error.contains("[default]"));
+        assert!(socket_error.contains("give the device its own name"));
+        assert!(thermometer_error.starts_with(DEFAULT_NAME_TAKEN));
+        assert!(thermometer_error.contains("[default_name]"));
+        assert_eq!(duplicate_error, thermometer_error);
+    }
+
+    #[test]
+    fn add_second_default_power_strip_fails_with_hint() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(PowerStrip::new())).unwrap();
+
+        let error = home.add_device("R01", Box::new(PowerStrip::new())).unwrap_err();
+
+        assert_eq!(
+            error,
+            format!("{} [default_strip], give the device its own name", DEFAULT_NAME_TAKEN)
+        );
+    }
+
+    #[test]
+    fn connect_device_names_missing_room_device() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        assert_eq!(
+            home.connect_cross_room("R01", "S01", "R01", "T02"),
+            Err(String::from("Room[R01] not contains device[T02]"))
+        );
+        assert_eq!(
+            home.connect_cross_room("R02", "S01", "R01", "T01"),
+            Err(String::from("Room[R02] not contains device[S01]"))
+        );
+        assert_eq!(
+            home.reparent_device("T01", "S02"),
+            Err(String::from("Room[R01] not contains device[S02]"))
+        );
+    }
+
+    #[test]
+    fn schema_json_with_format_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "power").unwrap();
+
+        let pretty = home.schema_json_with(JsonFormat::Pretty);
+        let compact = home.schema_json_with(JsonFormat::Compact);
+
+        assert_eq!(compact, home.schema_json());
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+        assert_eq!(
+            pretty,
+            concat!(
+                "{\n",
+                "  \"rooms\": [\n",
+                "    {\"name\": \"R01\", \"devices\": [\"S01\", \"T01\"]},\n",
+                "    {\"name\": \"R02\", \"devices\": []}\n",
+                "  ],\n",
+                "  \"links\": [\n",
+                "    {\"from\": \"S01\", \"to\": \"T01\", \"label\": \"power\"}\n",
+                "  ]\n",
+                "}"
+            )
+        );
+    }
+
+    #[test]
+    fn connect_device_subtree_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "T01", "T02").unwrap();
+
+        let node = home.connect_device_subtree("R01", "S01", "T03").unwrap();
+
+        let children: Vec<&str> = node.children.iter().map(|child| child.name.as_str()).collect();
+        assert_eq!(node.name, "S01");
+        assert_eq!(children, vec!["T01", "T03"]);
+        assert_eq!(node.children[0].children[0].name, "T02");
+        assert!(node.children[1].children.is_empty());
+        assert_eq!(home.subtree("S01"), Some(node));
+        assert!(home.connect_device_subtree("R01", "S01", "Unknown").is_err());
+    }
+
+    #[test]
+    fn room_devices_by_kind_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Lamp { name: String::from("L01") })).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S03", "S03 description", 1000.0))).unwrap();
+
+        assert_eq!(home.room_devices_by_kind("R01", DeviceKind::Socket), vec!["S01", "S02"]);
+        assert_eq!(home.room_devices_by_kind("R01", DeviceKind::Thermometer), vec!["T01"]);
+        assert_eq!(home.room_devices_by_kind("R01", DeviceKind::Other), vec!["L01"]);
+        assert!(home.room_devices_by_kind("R02", DeviceKind::Thermometer).is_empty());
+        assert!(home.room_devices_by_kind("Unknown", DeviceKind::Socket).is_empty());
+    }
+
+    #[test]
+    fn connect_device_with_parent_fails() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        let error = Err(String::from(
+            "Device[T01] already connected to device[S01], use reparent_device to move it",
+        ));
+        assert_eq!(home.can_connect("S02", "T01"), error);
+        assert_eq!(home.connect_device("R01", "S02", "T01").map(|_| ()), error);
+        assert_eq!(home.connect_device("R01", "S01", "T01"), Ok(false));
+        assert!(home.device_children("S02").is_empty());
+
+        home.reparent_device("T01", "S02").unwrap();
+        assert_eq!(home.device_children("S02"), vec!["T01"]);
+    }
+
+    #[test]
+    fn store_links_add_device_twice_fails() {
+        let mut store = StoreDeviceLinks::new();
+        store.add_room("R01");
+        store.add_room("R02");
+        store.add_device("R01", "S01").unwrap();
+        store.add_device("R01", "T01").unwrap();
+        store.connect_device("S01", "T01");
+
+        let error = Err(String::from("Device name [S01] already exists at schema"));
+        assert_eq!(store.add_device("R01", "S01"), error);
+        assert_eq!(store.add_device("R02", "S01"), error);
+
+        assert_eq!(store.members("R01").collect::<Vec<_>>(), vec!["S01", "T01"]);
+        assert_eq!(store.members("R02").count(), 0);
+        assert_eq!(store.connections("S01").collect::<Vec<_>>(), vec!["T01"]);
+    }
+
+    #[test]
+    fn power_strip_work() {
+        let mut first = Socket::from("S01", "S01 description", 1000.0);
+        first.set_power_consumption(300.0).unwrap();
+        first.power_on();
+        let mut second = Socket::from("S02", "S02 description", 500.0);
+        second.set_power_consumption(200.0).unwrap();
+        second.power_on();
+        let mut strip = PowerStrip::from("P01", "P01 description");
+        strip.add_socket(first).unwrap();
+        strip.add_socket(second).unwrap();
+
+        assert_eq!(strip.power_consumption(), 500.0);
+        strip.socket_mut("S02").unwrap()._power_off();
+        assert_eq!(strip.power_consumption(), 300.0);
+
+        let info = strip.info();
+        assert!(info.contains("[POWER STRIP] P01"));
+        assert!(info.contains("[SOCKET] S01"));
+        assert!(info.contains("[SOCKET] S02"));
+        assert_eq!(strip.status(), "OK");
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S03", "S03 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(strip)).unwrap();
+        home.connect_device("R01", "S03", "P01").unwrap();
+        assert_eq!(home.device("P01").unwrap().kind(), DeviceKind::PowerStrip);
+        assert_eq!(home.device_children("S03"), vec!["P01"]);
+    }
+
+    #[test]
+    fn power_report_work() {
+        fn socket(name: &str, power: f32, enabled: bool) -> Box<Socket> {
+            let mut socket = Socket::from(name, "description", 1000.0);
+            socket.set_power_consumption(power).unwrap();
+            if enabled {
+                socket.power_on();
+            }
+            Box::new(socket)
+        }
+
+        let mut strip = PowerStrip::from("P01", "P01 description");
+        strip.add_socket(*socket("S05", 50.0, true)).unwrap();
+        strip.add_socket(*socket("S06", 70.0, false)).unwrap();
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_room(Room::new("R03")).unwrap();
+        home.add_device("R01", socket("S01", 100.0, true)).unwrap();
+        home.add_device("R01", socket("S02", 200.0, false)).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(strip)).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "S01", "P01").unwrap();
+        home.add_device("R02", socket("S03", 300.0, true)).unwrap();
+        home.add_device("R02", socket("S04", 400.0, true)).unwrap();
+
+        assert_eq!(
+            home.power_report(),
+            vec![
+                (String::from("R01"), 150.0),
+                (String::from("R02"), 700.0),
+                (String::from("R03"), 0.0),
+            ]
+        );
+
+        let mut strip = PowerStrip::from("P02", "P02 description");
+        strip.add_socket(*socket("S07", 400.0, true)).unwrap();
+        home.add_device("R03", Box::new(strip)).unwrap();
+        home.connect_cross_room("R01", "S01", "R03", "P02").unwrap();
+
+        assert_eq!(
+            home.power_report(),
+            vec![
+                (String::from("R01"), 150.0),
+                (String::from("R02"), 700.0),
+                (String::from("R03"), 400.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn connect_device_with_warnings_work() {
+        let mut enabled = Socket::from("S02", "S02 description", 1000.0);
+        enabled.power_on();
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(enabled)).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+
+        let outcome = home.connect_device_with_warnings("R01", "S01", "T01").unwrap();
+        assert!(outcome.created);
+        assert_eq!(
+            outcome.warnings,
+            vec![String::from("Socket[S01] is off, device[T01] may be unpowered")]
+        );
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+
+        let outcome = home.connect_device_with_warnings("R01", "S01", "T01").unwrap();
+        assert!(!outcome.created);
+        assert!(outcome.warnings.is_empty());
+
+        let outcome = home.connect_device_with_warnings("R01", "S02", "T02").unwrap();
+        assert!(outcome.created);
+        assert!(outcome.warnings.is_empty());
+
+        let outcome = home.connect_device_with_warnings("R01", "T01", "T02");
+        assert!(outcome.is_err());
+    }
+
+    #[test]
+    fn collect_schema_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "T01", "T02").unwrap();
+
+        let schema = home.collect_schema();
+
+        assert_eq!(
+            schema,
+            concat!(
+                "[HOME] home\n",
+                "- [ROOM] R01\n",
+                "\t- [DEVICE] S01\n",
+                "\t\t- [CONNECTED] T01\n",
+                "\t\t\t- [CONNECTED] T02\n",
+                "- [ROOM] R02\n",
+            )
+        );
+        assert!(!schema.contains("In development"));
+    }
+
+    #[test]
+    fn adjacency_list_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "T01", "T02").unwrap();
+
+        let node = |name: &str, children: &[&str]| {
+            (String::from(name), children.iter().map(|child| child.to_string()).collect())
+        };
+        assert_eq!(
+            home.adjacency_list(),
+            vec![
+                node(HOME_NAME, &["R01", "R02"]),
+                node("R01", &["S01", "T01", "T02"]),
+                node("R02", &["S02"]),
+                node("S01", &["T01"]),
+                node("T01", &["T02"]),
+                node("T02", &[]),
+                node("S02", &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn socket_firmware_version_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        assert!(!socket.info().contains("Firmware version"));
+        socket.set_firmware_version(Some("2.1.3"));
+        assert!(socket.info().contains("\tFirmware version = 2.1.3\n]"));
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(socket)).unwrap();
+        let memento = home.take_snapshot();
+        home.remove_device("S01").unwrap();
+        home.restore_snapshot(memento);
+
+        let device = home.device("S01").unwrap();
+        let socket = device.as_socket().unwrap();
+        assert_eq!(socket.firmware_version(), Some("2.1.3"));
+        assert!(socket.info().contains("Firmware version = 2.1.3"));
+    }
+
+    #[test]
+    fn connect_and_disconnect_device_are_inverse() {
+        let names = ["S01", "S02", "T01", "T02", "T03", "T04"];
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        for name in names.iter() {
+            let device: Box<dyn Device> = if name.starts_with('S') {
+                Box::new(Socket::from(name, "description", 1000.0))
+            } else {
+                Box::new(Thermometer::from(name, "description"))
+            };
+            home.add_device("R01", device).unwrap();
+        }
+        home.connect_device_labeled("R01", "S01", "T01", "power").unwrap();
+        home.connect_device("R01", "S01", "T02").unwrap();
+        let baseline = (home.adjacency_list(), home.schema_json());
+
+        let mut seed: usize = 17;
+        let mut connected = 0;
+        for _ in 0..200 {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            let parent = names[seed % names.len()];
+            let child = names[(seed / names.len()) % names.len()];
+            if home.device_children(parent).contains(&child.to_string()) {
+                continue;
+            }
+
+            if home.connect_device_labeled("R01", parent, child, "label").is_ok() {
+                connected += 1;
+                home.disconnect_device(parent, child).unwrap();
+            }
+            assert_eq!((home.adjacency_list(), home.schema_json()), baseline);
+        }
+        assert!(connected > 0);
+
+        assert!(home.disconnect_device("S02", "T03").is_err());
+        assert!(home.disconnect_device("Unknown", "T01").is_err());
+        assert_eq!((home.adjacency_list(), home.schema_json()), baseline);
+    }
+
+    #[test]
+    fn find_devices_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+
+        assert_eq!(home.find_devices("S0"), vec!["S01", "S02"]);
+        assert_eq!(home.find_devices("s0"), vec!["S01", "S02"]);
+        assert_eq!(home.find_devices("01"), vec!["S01", "T01"]);
+        assert!(home.find_devices("X").is_empty());
+    }
+
+    #[test]
+    fn boxed_device_clone_is_independent() {
+        let mut origin: Box<dyn Device> = Box::new(Socket::from("S01", "S01 description", 1000.0));
+        let mut copy = origin.clone();
+
+        copy.as_socket_mut().unwrap().set_power_consumption(500.0).unwrap();
+        copy.as_socket_mut().unwrap().power_on();
+        origin.set_name("S02", RenameToken::new());
+
+        let origin = origin.as_socket().unwrap();
+        let copy = copy.as_socket().unwrap();
+        assert_eq!((origin.name(), copy.name()), ("S02", "S01"));
+        assert_eq!((origin.power_consumption(), copy.power_consumption()), (0.0, 500.0));
+        assert!(!origin.is_enabled());
+        assert!(copy.is_enabled());
+    }
+
+    #[test]
+    fn reorder_room_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_room(Room::new("R03")).unwrap();
+        home.add_device("R03", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        home.reorder_room("R03", 0).unwrap();
+        assert_eq!(home.rooms(), vec!["R03", "R01", "R02"]);
+        assert_eq!(home.adjacency_list()[0].1, vec!["R03", "R01", "R02"]);
+        assert!(home.collect_schema().starts_with("[HOME] home\n- [ROOM] R03\n\t- [DEVICE] S01\n"));
+
+        home.reorder_room("R03", 100).unwrap();
+        assert_eq!(home.rooms(), vec!["R01", "R02", "R03"]);
+        assert_eq!(home.adjacency_list()[0].1, vec!["R01", "R02", "R03"]);
+
+        assert_eq!(
+            home.reorder_room("R04", 0),
+            Err(String::from("Room with name [R04] not found"))
+        );
+    }
+
+    #[test]
+    fn device_snapshot_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        socket.set_power_consumption(250.0).unwrap();
+        socket.power_on();
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(socket)).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        let snapshot = home.device_snapshot("S01").unwrap();
+        let handle = thread::spawn(move || -> DeviceSnapshot { snapshot });
+        let snapshot = handle.join().unwrap();
+
+        assert_eq!(snapshot.name, "S01");
+        assert_eq!(snapshot.kind, DeviceKind::Socket);
+        assert_eq!(snapshot.status, "OK");
+        assert!(snapshot.info.contains("[SOCKET] S01"));
+        assert_eq!(snapshot.enabled, Some(true));
+        assert_eq!(snapshot.power_consumption, Some(250.0));
+        assert_eq!(snapshot.measurement, None);
+
+        let snapshot = home.device_snapshot("T01").unwrap();
+        assert_eq!(snapshot.kind, DeviceKind::Thermometer);
+        assert_eq!(snapshot.enabled, None);
+        assert_eq!(snapshot.measurement, Some(String::from("sample info")));
+    }
+
+    #[test]
+    fn schema_stats_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_room(Room::new("R03")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        for name in ["T01", "T02", "T03", "T04"] {
+            home.add_device("R01", Box::new(Thermometer::from(name, "description"))).unwrap();
+        }
+        home.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T05", "T05 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_device("R01", "S01", "T02").unwrap();
+        home.connect_device("R01", "S01", "T03").unwrap();
+        home.connect_device("R01", "T01", "T04").unwrap();
+        home.connect_cross_room("R01", "T04", "R02", "T05").unwrap();
+
+        assert_eq!(
+            home.schema_stats(),
+            SchemaStats {
+                nodes: 7,
+                edges: 5,
+                max_depth: 3,
+                max_fanout: 3,
+                rooms: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn store_devices_id_work() {
+        let mut store = StoreDevices::new();
+        let s01 = store.add_device(Box::new(Socket::from("S01", "S01 description", 1000.0)));
+        let s02 = store.add_device(Box::new(Socket::from("S02", "S02 description", 1000.0)));
+        let t01 = store.add_device(Box::new(Thermometer::from("T01", "T01 description")));
+
+        assert_eq!(store.get_by_id(t01).unwrap().name(), "T01");
+        assert_eq!(store.id_of("S02"), Some(s02));
+
+        store.remove_device("S01").unwrap();
+        store.rename_device("T01", "T02").unwrap();
+        assert!(store.get_by_id(s01).is_none());
+        assert_eq!(store.get_by_id(s02).unwrap().name(), "S02");
+        assert_eq!(store.get_by_id(t01).unwrap().name(), "T02");
+
+        assert_eq!(store.remove_by_id(s02).unwrap().name(), "S02");
+        assert!(store.remove_by_id(s02).is_err());
+        let t03 = store.add_device(Box::new(Thermometer::from("T03", "T03 description")));
+        assert_ne!(t03, s01);
+        assert_eq!(store.get_by_id(t01).unwrap().name(), "T02");
+    }
+
+    #[test]
+    fn home_device_id_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        let id: DeviceId = home.device_id("T01").unwrap();
+
+        home.rename_device("T01", "T02").unwrap();
+        assert_eq!(home.device_by_id(id).unwrap().name(), "T02");
+
+        home.remove_device_by_id(id).unwrap();
+        assert!(home.device("T02").is_none());
+        assert!(home.device_children("S01").is_empty());
+        assert!(home.validate().is_empty());
+        assert!(home.remove_device_by_id(id).is_err());
+    }
+
+    #[test]
+    fn collect_schema_with_format_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "power").unwrap();
+        let format = SchemaFormat {
+            indent: String::from("  "),
+            bullet: String::from("*"),
+        };
+
+        assert_eq!(
+            home.collect_schema_with(&format),
+            concat!(
+                "[HOME] home\n",
+                "* [ROOM] R01\n",
+                "  * [DEVICE] S01\n",
+                "    * [CONNECTED] T01 (power)\n",
+            )
+        );
+    }
+
+    #[test]
+    fn write_report_and_schema_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "power").unwrap();
+
+        let mut report: Vec<u8> = Vec::new();
+        home.write_report(&mut report).unwrap();
+        let mut schema: Vec<u8> = Vec::new();
+        home.write_schema(&mut schema).unwrap();
+
+        assert_eq!(String::from_utf8(report).unwrap(), home.collect_report());
+        assert_eq!(String::from_utf8(schema).unwrap(), home.collect_schema());
+        let measurements = "Measurements:\nT01: sample info\nT02: sample info\n";
+        assert!(home.collect_report().ends_with(measurements));
+        assert!(home.collect_schema().contains("\t\t- [CONNECTED] T01 (power)\n"));
+    }
+
+    #[test]
+    fn socket_voltage_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        assert!(!socket.info().contains("Voltage"));
+        socket.set_voltage(Some(230.0));
+        assert!(socket.info().contains("\tVoltage = 230\n"));
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(socket)).unwrap();
+
+        let snapshot = home.device_snapshot("S01").unwrap();
+        assert_eq!(snapshot.voltage, Some(230.0));
+        assert!(snapshot.info.contains("\tVoltage = 230\n"));
+
+        let memento = home.take_snapshot();
+        home.remove_device("S01").unwrap();
+        assert!(home.device_snapshot("S01").is_none());
+        home.restore_snapshot(memento);
+        assert_eq!(home.device_snapshot("S01").unwrap().voltage, Some(230.0));
+    }
+
+    #[test]
+    fn connect_devices_batch_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T03", "T03 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T04", "T04 description"))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T05", "T05 description"))).unwrap();
+
+        home.connect_devices_batch(&[("S01", "T01"), ("T01", "T02")]).unwrap();
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+        assert_eq!(home.device_children("T01"), vec!["T02"]);
+
+        let schema = home.collect_schema();
+        let result = home.connect_devices_batch(&[("T03", "T04"), ("T04", "T03")]);
+        assert!(result.unwrap_err().contains("cycle"));
+        let result = home.connect_devices_batch(&[("S01", "T03"), ("S01", "T05")]);
+        let same_room_error = home.connect_device("R01", "S01", "T05").unwrap_err();
+        assert_eq!(result.unwrap_err(), same_room_error);
+        assert_eq!(home.collect_schema(), schema);
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+        assert!(home.device_children("T03").is_empty());
+        assert!(home.device_children("T04").is_empty());
+    }
+
+
+    #[test]
+    fn room_report_work() {
+        let mut socket = Socket::from("S01", "S01 description", 1000.0);
+        socket.set_power_consumption(300.0).unwrap();
+        socket.power_on();
+
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_room(Room::new("R02")).unwrap();
+        home.add_device("R01", Box::new(socket)).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R02", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+        home.connect_cross_room("R01", "T01", "R02", "T02").unwrap();
+
+        let report = home.room_report("R01").unwrap();
+        assert!(report.starts_with("Generated report about room [R01]:\n"));
+        assert!(report.contains("[SOCKET] S01"));
+        assert!(report.contains("[THERMOMETER] T01"));
+        assert!(!report.contains("T02"));
+        assert!(report.ends_with("Total power: 300\n"));
+
+        let report = home.room_report("R02").unwrap();
+        assert!(report.contains("T02") && !report.contains("S01") && !report.contains("T01"));
+        assert!(report.ends_with("Total power: 0\n"));
+        assert!(home.room_report("R03").is_err());
+    }
+
+    #[test]
+    fn subtree_max_depth_work() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        let mut parent = String::from("S01");
+        for index in 1..=4 {
+            let name = format!("T{:02}", index);
+            let device = Thermometer::from(name.as_str(), "description");
+            home.add_device("R01", Box::new(device)).unwrap();
+            home.connect_device("R01", parent.as_str(), name.as_str()).unwrap();
+            parent = name;
+        }
+
+        let depth = |mut node: &SchemaNode| {
+            let mut depth = 0;
+            while let Some(child) = node.children.first() {
+                node = child;
+                depth += 1;
+            }
+            depth
+        };
+        assert_eq!(depth(&home.subtree("S01").unwrap()), 4);
+
+        home.set_max_depth(2);
+        let node = home.subtree("S01").unwrap();
+        assert_eq!(depth(&node), 2);
+        assert_eq!(node.children[0].children[0].name, "T02");
+        assert_eq!(depth(&home.subtree("T02").unwrap()), 2);
+    }
+
+    #[test]
+    fn device_copy_doesnt_lock_home() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+
+        if let Some(device) = home.device("S01") {
+            home.remove_device(device.name()).unwrap();
+        }
+
+        assert!(home.device("S01").is_none());
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn import_csv_keeps_existing_devices_on_error() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.connect_device("R01", "S01", "T01").unwrap();
+
+        let result = home.import_csv("R02,socket,S02,1000\nR01,socket,S01,1500");
+
+        assert!(result.unwrap_err().starts_with("Line 2: "));
+        assert_eq!(home.rooms(), vec!["R01"]);
+        assert_eq!(home.devices("R01"), vec!["S01", "T01"]);
+        assert_eq!(home.device_children("S01"), vec!["T01"]);
+        assert!(home.validate().is_empty());
+    }
+
+    #[test]
+    fn reparent_device_to_same_parent_keeps_order() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T02", "T02 description"))).unwrap();
+        home.connect_device_labeled("R01", "S01", "T01", "controls").unwrap();
+        home.connect_device("R01", "S01", "T02").unwrap();
+
+        assert_eq!(home.reparent_device("T01", "S01"), Ok(()));
+
+        assert_eq!(home.device_children("S01"), vec!["T01", "T02"]);
+        assert_eq!(home.connection_label("S01", "T01").as_deref(), Some("controls"));
+    }
+
+    #[test]
+    fn reparent_not_connected_device_fails() {
+        let home = Home::new("home");
+        home.add_room(Room::new("R01")).unwrap();
+        home.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        home.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+
+        assert_eq!(
+            home.reparent_device("T01", "S01"),
+            Err(String::from("Device[T01] is not connected to any device"))
+        );
+        assert!(home.device_children("S01").is_empty());
+    }
+
+    #[test]
+    fn remove_room_cascades_devices_from_both_stores() {
+        let room_01 = Room::new("R01");
+        let room_02 = Room::new("R02");
+        let mut service = ServiceDeviceManagement::new();
+        service.add_room(&room_01).unwrap();
+        service.add_room(&room_02).unwrap();
+        service.add_device("R01", Box::new(Socket::from("S01", "S01 description", 1000.0))).unwrap();
+        service.add_device("R01", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        service.add_device("R02", Box::new(Socket::from("S02", "S02 description", 1000.0))).unwrap();
+        service.connect_device("R01", "S01", "T01").unwrap();
+
+        service.remove_room("R01").unwrap();
+
+        let names: Vec<&str> = service.all_devices().map(|device| device.name()).collect();
+        assert_eq!(names, vec!["S02"]);
+        assert!(service.get_device("T01").is_none());
+        assert!(service.device_path("T01").is_none());
+        assert!(service.device_children("S01").is_empty());
+        assert!(service.validate().is_empty());
+
+        service.add_device("R02", Box::new(Thermometer::from("T01", "T01 description"))).unwrap();
+        service.connect_device("R02", "S02", "T01").unwrap();
+        assert_eq!(service.device_path("T01").unwrap(), vec!["R02", "S02", "T01"]);
+    }
 }
@@ -1,5 +1,85 @@
-use crate::devices::Device;
-use crate::services::ServiceDeviceManagement;
+use std::{
+    io::{self, Write},
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::devices::power_strip::PowerStrip;
+use crate::devices::socket::Socket;
+use crate::devices::thermometer::Thermometer;
+use crate::devices::{Device, DeviceKind};
+use crate::stores::DeviceId;
+use crate::services::{
+    ConnectOutcome, Inconsistency, JsonFormat, SchemaFormat, SchemaNode, SchemaStats,
+    ServiceDeviceManagement,
+};
+
+/// Struct with summary statistics of the Home
+///
+/// Contains counts of rooms and devices and totals of sockets.
+/// Sockets of power strips are counted as sockets too
+#[derive(Debug)]
+pub struct HomeStats {
+    /// Count of rooms
+    pub rooms: usize,
+    /// Count of all devices, power strip is counted as one device
+    pub devices: usize,
+    /// Count of sockets, including sockets of power strips
+    pub sockets: usize,
+    /// Count of thermometers
+    pub thermometers: usize,
+    /// Summary power consumption of enabled sockets, same as total of power_report
+    pub total_power: f32,
+    /// Count of enabled sockets, including sockets of power strips
+    pub enabled_sockets: usize,
+}
+
+/// Owned copy of device state, which can be sent to another thread
+///
+/// Kind-specific fields are None for devices of other kinds
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceSnapshot {
+    pub name: String,
+    pub kind: DeviceKind,
+    pub status: String,
+    pub info: String,
+    /// Power state of socket
+    pub enabled: Option<bool>,
+    /// Power consumption of socket or power strip
+    pub power_consumption: Option<f32>,
+    /// Voltage of socket, if it is tracked
+    pub voltage: Option<f32>,
+    /// Data of Measurable device
+    pub measurement: Option<String>,
+}
+
+impl DeviceSnapshot {
+    fn of(device: &dyn Device) -> Self {
+        let socket = device.as_socket();
+        let power_consumption = match (socket, device.as_power_strip()) {
+            (Some(socket), _) => Some(socket.power_consumption()),
+            (_, Some(strip)) => Some(strip.power_consumption()),
+            _ => None,
+        };
+
+        DeviceSnapshot {
+            name: String::from(device.name()),
+            kind: device.kind(),
+            status: device.status(),
+            info: device.info(),
+            enabled: socket.map(|socket| socket.is_enabled()),
+            power_consumption,
+            voltage: socket.and_then(|socket| socket.voltage()),
+            measurement: device.as_measurable().map(|measurable| String::from(measurable.data())),
+        }
+    }
+}
+
+/// Saved state of the home rooms and devices
+///
+/// Created by Home::take_snapshot and applied by Home::restore_snapshot
+pub struct HomeMemento {
+    state: HomeState,
+}
 
 /// Home structure
 ///
@@ -7,12 +87,91 @@ use crate::services::ServiceDeviceManagement;
 /// On first level can placed rooms and unconnected devices.
 /// Next levels are for connected devices
 ///
+/// Rooms and services of the home are placed behind internal RwLock,
+/// so Home can be shared between threads as `Arc<Home>` without outer lock.
+/// Readers don't block each other, only changes of the home take exclusive access.
+///
 pub struct Home {
     name: String,
+    state: RwLock<HomeState>,
+}
+
+/// Rooms and services of the home, which are changed together under one lock
+#[derive(Clone)]
+struct HomeState {
     rooms: Vec<Room>,
     manager_devices: ServiceDeviceManagement,
 }
 
+impl HomeState {
+    fn room(&self, name: &str) -> Option<&Room> {
+        self.rooms.iter().find(|&room| room.name.as_str() == name)
+    }
+
+    fn require_room(&self, room_name: &str) -> Result<&Room, String> {
+        match self.room(room_name) {
+            Some(room) => Ok(room),
+            None => Err(format!("Room with name [{}] not found", room_name)),
+        }
+    }
+
+    fn add_room(&mut self, room: Room) -> Result<(), String> {
+        self.manager_devices.add_room(&room)?;
+        self.rooms.push(room);
+        Ok(())
+    }
+
+    fn ensure_room(&mut self, room_name: &str) -> Result<bool, String> {
+        if self.room(room_name).is_some() {
+            return Ok(false);
+        }
+
+        self.add_room(Room::new(room_name))?;
+        Ok(true)
+    }
+
+    fn remove_room(&mut self, room_name: &str) -> Result<Vec<String>, String> {
+        match self.rooms.iter().position(|room| room.name.as_str() == room_name) {
+            Some(index) => {
+                let removed_devices = self.manager_devices.remove_room(room_name)?;
+                self.rooms.remove(index);
+                Ok(removed_devices)
+            },
+            None => {
+                let message = format!("Room with name [{}] not found", room_name);
+                Err(message)
+            }
+        }
+    }
+
+    /// Method return summary power consumption of enabled sockets and power strips of the room
+    ///
+    /// Device connected from another room is counted only in its own room
+    fn room_power(&self, room_name: &str) -> f32 {
+        self.placed_devices_recursive(room_name)
+            .iter()
+            .filter_map(|device_name| self.manager_devices.get_device(device_name))
+            .map(|device| match (device.as_socket(), device.as_power_strip()) {
+                (Some(socket), _) if socket.is_enabled() => socket.power_consumption(),
+                (_, Some(strip)) => strip.power_consumption(),
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// Method return devices placed in the room in order of connections
+    ///
+    /// Connections are not followed into devices placed in other rooms
+    fn placed_devices_recursive(&self, room_name: &str) -> Vec<String> {
+        let placed: Vec<&String> = self.manager_devices.get_devices(room_name).collect();
+        self.manager_devices
+            .get_devices_recursive(room_name)
+            .into_iter()
+            .filter(|device_name| placed.contains(&device_name))
+            .collect()
+    }
+}
+
 impl Home {
     /// Method create new example of Home with specific name
     ///
@@ -27,9 +186,66 @@ impl Home {
 
         Self {
             name: String::from(name),
-            rooms: vec![],
-            manager_devices,
+            state: RwLock::new(HomeState {
+                rooms: vec![],
+                manager_devices,
+            }),
+        }
+    }
+
+    /// Method create new example of Home with preallocated place for rooms and devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    ///
+    /// let home = Home::with_capacity("MY best Home", 10, 100);
+    ///
+    /// # assert_eq!(home.name(), "MY best Home");
+    /// # assert!(home.rooms().is_empty());
+    /// ```
+    pub fn with_capacity(name: &str, rooms: usize, devices: usize) -> Self {
+        let manager_devices = ServiceDeviceManagement::with_capacity(rooms, devices);
+
+        Self {
+            name: String::from(name),
+            state: RwLock::new(HomeState {
+                rooms: Vec::with_capacity(rooms),
+                manager_devices,
+            }),
+        }
+    }
+
+    /// Method create new Home with one room, which contains all devices
+    ///
+    /// If any device can't be added (e.g. duplicate name) - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::Device;
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Home;
+    ///
+    /// let devices: Vec<Box<dyn Device>> = vec![
+    ///     Box::new(Socket::from("S01", "Description of S01", 1000.0)),
+    ///     Box::new(Socket::from("S02", "Description of S02", 1000.0)),
+    /// ];
+    /// let home = Home::from_devices("MY best Home", "Kitchen", devices).unwrap();
+    ///
+    /// # assert_eq!(home.devices("Kitchen"), vec!["S01", "S02"]);
+    /// ```
+    pub fn from_devices(
+        name: &str,
+        room_name: &str,
+        devices: impl IntoIterator<Item = Box<dyn Device>>,
+    ) -> Result<Self, String> {
+        let home = Self::new(name);
+        home.add_room(Room::new(room_name))?;
+        for device in devices {
+            home.add_device(room_name, device)?;
         }
+
+        Ok(home)
     }
 
     /// Method return name of the Home
@@ -47,13 +263,45 @@ impl Home {
         self.name.as_str()
     }
 
+    /// Method change name of the Home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    ///
+    /// let mut home = Home::new("MY best Home");
+    /// home.set_name("MY new Home");
+    ///
+    /// # assert_eq!(home.name(), "MY new Home")
+    /// ```
+    pub fn set_name(&mut self, name: &str) {
+        self.name = String::from(name);
+    }
+
+    /// Method check the Home contains no rooms and no devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// # assert!(home.is_empty());
+    ///
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// # assert!(!home.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        let state = self.state();
+        state.rooms.is_empty() && state.manager_devices.all_devices().len() == 0
+    }
+
     /// Method add new room to the Home
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room = Room::new("Kitchen");
     ///
     /// home.add_room(room).unwrap();
@@ -61,23 +309,38 @@ impl Home {
     /// # assert!(home.rooms().contains(&String::from("Kitchen")));
     /// # assert!(home.add_room(Room::new("Kitchen")).is_err())
     /// ```
-    pub fn add_room(&mut self, room: Room) -> Result<(), String> {
-        match self.manager_devices.add_room(&room) {
-            Ok(_) => {
-                self.rooms.push(room);
-                Ok(())
-            }
-            Err(error) => Err(error),
-        }
+    pub fn add_room(&self, room: Room) -> Result<(), String> {
+        self.state_mut().add_room(room)
+    }
+
+    /// Method add room to the Home, if Home not contains it
+    ///
+    /// Returns true, if room was created
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    ///
+    /// let home = Home::new("MY best Home");
+    ///
+    /// # assert_eq!(home.ensure_room("Kitchen"), Ok(true)); // first call creates room
+    /// # assert_eq!(home.ensure_room("Kitchen"), Ok(false)); // second call does nothing
+    /// # assert_eq!(home.rooms(), vec!["Kitchen"]);
+    /// ```
+    pub fn ensure_room(&self, room_name: &str) -> Result<bool, String> {
+        self.state_mut().ensure_room(room_name)
     }
 
     /// Method remove room from home
     ///
+    /// All devices of the room are removed too.
+    /// Returns names of removed devices in order of the room
+    ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room_name = "Kitchen";
     /// let room = Room::new(room_name);
     ///
@@ -87,13 +350,38 @@ impl Home {
     /// # assert!(!home.rooms().contains(&String::from(room_name))); // normal remove is OK
     /// # assert!(home.remove_room(room_name).is_err()) // second remove is KO
     /// ```
-    pub fn remove_room(&mut self, room_name: &str) -> Result<(), String> {
-        match self.rooms.iter().position(|room| room.name.as_str() == room_name) {
+    pub fn remove_room(&self, room_name: &str) -> Result<Vec<String>, String> {
+        self.state_mut().remove_room(room_name)
+    }
+
+    /// Method move room to new position in rooms of the home
+    ///
+    /// New position is clamped to bounds of rooms list.
+    /// Order of rooms is used by schema and reports
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    ///
+    /// home.reorder_room("Hall", 0).unwrap();
+    ///
+    /// # assert_eq!(home.rooms(), vec!["Hall", "Kitchen"]);
+    /// # assert!(home.reorder_room("Unknown", 0).is_err());
+    /// ```
+    pub fn reorder_room(&self, room_name: &str, new_index: usize) -> Result<(), String> {
+        let mut state = self.state_mut();
+        match state.rooms.iter().position(|room| room.name.as_str() == room_name) {
             Some(index) => {
-                self.manager_devices.remove_room(room_name)?;
-                self.rooms.remove(index);
+                state.manager_devices.reorder_room(room_name, new_index)?;
+                let room = state.rooms.remove(index);
+                let new_index = new_index.min(state.rooms.len());
+                state.rooms.insert(new_index, room);
                 Ok(())
-            },
+            }
             None => {
                 let message = format!("Room with name [{}] not found", room_name);
                 Err(message)
@@ -101,18 +389,18 @@ impl Home {
         }
     }
 
-    /// Method return struct Room by specific name
+    /// Method return copy of struct Room by specific name
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room = Room::new("Kitchen");
     ///
     /// home.add_room(room).unwrap();
     ///
-    /// let room_opt: Option<&Room> = home.room("Kitchen");
+    /// let room_opt: Option<Room> = home.room("Kitchen");
     ///
     /// match room_opt {
     ///     Some(room_found) => println!("Found room: {}", room_found.name()),
@@ -121,8 +409,8 @@ impl Home {
     ///
     /// # assert_eq!(home.room("Kitchen").unwrap().name(), "Kitchen")
     /// ```
-    pub fn room(&self, name: &str) -> Option<&Room> {
-        self.rooms.iter().find(|&room| room.name.as_str() == name)
+    pub fn room(&self, name: &str) -> Option<Room> {
+        self.state().room(name).cloned()
     }
 
     /// Method return all rooms names
@@ -131,7 +419,7 @@ impl Home {
     /// ```
     /// use crate::home::places::{Home, Room};
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room = Room::new("Kitchen");
     ///
     /// home.add_room(room).unwrap();
@@ -143,180 +431,1745 @@ impl Home {
     /// # assert!(!home.rooms().is_empty())
     /// ```
     pub fn rooms(&self) -> Vec<String> {
-        self.rooms.iter().map(|room| room.name.clone()).collect()
+        self.state().rooms.iter().map(|room| room.name.clone()).collect()
     }
 
-    /// Method add new device to the room
+    /// Method return page of rooms names in order of adding
+    ///
+    /// Offset and limit are clamped to bounds of rooms list
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
-    /// use crate::home::devices::socket::Socket;
     ///
-    /// let mut home = Home::new("MY best Home");
-    /// let room_name = "Kitchen";
-    /// let room = Room::new(room_name);
-    /// home.add_room(room).unwrap();
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_room(Room::new("Bedroom")).unwrap();
     ///
-    /// let device = Socket::new();
-    /// let result: Result<(), String> = home.add_device(room_name, Box::new(device));
+    /// for room in home.rooms_page(1, 10) {
+    ///     println!("{}", room);
+    /// }
     ///
-    /// # assert!(result.is_ok()); // normal add is OK
-    /// # assert!(home.add_device(room_name, Box::new(Socket::new())).is_err()); // duplicate add is KO
-    /// # assert!(home.add_device("Unknown room", Box::new(Socket::new())).is_err()) // add to the unknown room is KO
+    /// # assert_eq!(home.rooms_page(1, 10), vec!["Hall", "Bedroom"]);
+    /// # assert!(home.rooms_page(5, 10).is_empty());
     /// ```
-    pub fn add_device(&mut self, room_name: &str, device: Box<dyn Device>) -> Result<(), String> {
-        self.manager_devices.add_device(room_name, device)
+    pub fn rooms_page(&self, offset: usize, limit: usize) -> Vec<String> {
+        let state = self.state();
+        let start = offset.min(state.rooms.len());
+        let end = start.saturating_add(limit).min(state.rooms.len());
+        state.rooms[start..end].iter().map(|room| room.name.clone()).collect()
     }
 
-    /// Method remove device from home
+    /// Method check the room contains no devices
+    ///
+    /// If room found - return Some
+    /// If not - return None
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
-    /// use crate::home::devices::socket::Socket;
-    ///
-    /// let mut home = Home::new("MY best Home");
-    /// let room_name = "Kitchen";
-    /// let room = Room::new(room_name);
-    /// home.add_room(room).unwrap();
-    ///
-    /// home.add_device(room_name, Box::new(Socket::from("S01", "S01 Description", 1000.0))).unwrap();
-    /// home.add_device(room_name, Box::new(Socket::from("S02", "S02 Description", 1000.0))).unwrap();
-    /// home.add_device(room_name, Box::new(Socket::from("S03", "S03 Description", 1000.0))).unwrap();
-    /// home.add_device(room_name, Box::new(Socket::from("S04", "S04 Description", 1000.0))).unwrap();
     ///
-    /// let result = home.remove_device("S03");
-    /// let devices = home.devices(room_name);
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
     ///
-    /// # assert!(result.is_ok());
-    /// # assert!(!devices.contains(&String::from("S03")));
-    /// # assert_eq!(devices.len(), 3);
+    /// # assert_eq!(home.room_is_empty("Kitchen"), Some(true));
+    /// # assert_eq!(home.room_is_empty("Unknown room"), None);
     /// ```
-    pub fn remove_device(&mut self, device_name: &str) -> Result<(), String> {
-        self.manager_devices.remove_device(device_name)
+    pub fn room_is_empty(&self, room_name: &str) -> Option<bool> {
+        let state = self.state();
+        state.room(room_name)?;
+        let is_empty = state.manager_devices.get_devices(room_name).next().is_none();
+        Some(is_empty)
     }
 
-    /// Method connects one device to another
+    /// Method return each room with count of devices placed in it
     ///
-    /// Method is not tested
-    pub fn connect_device(
-        &mut self,
-        room_name: &str,
-        device_connects_to: &str,
-        device_connected: &str,
-    ) -> Result<(), String> {
-        self.manager_devices
-            .connect_device(room_name, device_connects_to, device_connected)
-    }
-
-    /// Method find device by its name
+    /// Rooms are returned in order of adding
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     /// use crate::home::devices::socket::Socket;
     ///
-    /// let mut home = Home::new("MY best Home");
-    /// let room_name = "Kitchen";
-    /// let room = Room::new(room_name);
-    /// home.add_room(room).unwrap();
-    ///
-    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
-    /// home.add_device(room_name, Box::new(device)).unwrap();
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "", 1000.0))).unwrap();
     ///
-    /// let device_opt = home.device("Socket");
-    /// match device_opt {
-    ///     Some(device_found) => println!("Found device with name {}", device_found.name()),
-    ///     None => panic!("Not found device!")
+    /// for (room, count) in home.device_count_by_room() {
+    ///     println!("{}: {}", room, count);
     /// }
     ///
-    /// # assert!(home.device("Socket").is_some()); // normal find is OK
-    /// # assert!(home.device("Unknown device").is_none()); // find of unknown device is KO
+    /// # assert_eq!(
+    /// #     home.device_count_by_room(),
+    /// #     vec![(String::from("Kitchen"), 1), (String::from("Hall"), 0)]
+    /// # );
     /// ```
-    pub fn device(&self, device_name: &str) -> Option<&dyn Device> {
-        self.manager_devices.get_device(device_name)
+    pub fn device_count_by_room(&self) -> Vec<(String, usize)> {
+        let state = self.state();
+        state
+            .rooms
+            .iter()
+            .map(|room| {
+                let count = state.manager_devices.get_devices(room.name.as_str()).count();
+                (room.name.clone(), count)
+            })
+            .collect()
     }
 
-    /// Method return list of all devices names in the specific room
+    /// Method pass all rooms names to the closure without cloning them
+    ///
+    /// Closure is called with read lock of the home, so it must not change the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    ///
+    /// home.with_rooms(|rooms| {
+    ///     for room in rooms {
+    ///         println!("{}", room);
+    ///     }
+    /// });
+    ///
+    /// # assert_eq!(home.with_rooms(|rooms| rooms.map(String::from).collect::<Vec<String>>()), vec!["Kitchen"]);
+    /// ```
+    pub fn with_rooms<R>(&self, f: impl FnOnce(Box<dyn Iterator<Item = &str> + '_>) -> R) -> R {
+        let state = self.state();
+        f(Box::new(state.rooms.iter().map(Room::name)))
+    }
+
+    /// Method add new device to the room
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     /// use crate::home::devices::socket::Socket;
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room_name = "Kitchen";
     /// let room = Room::new(room_name);
     /// home.add_room(room).unwrap();
     ///
-    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
-    /// home.add_device(room_name, Box::new(device)).unwrap();
-    ///
-    /// let devices_names = home.devices(room_name);
-    ///
-    /// for device_name in devices_names {
-    ///     println!("{}", device_name);
-    /// }
+    /// let device = Socket::new();
+    /// let result: Result<(), String> = home.add_device(room_name, Box::new(device));
     ///
-    /// # assert!(!home.devices(room_name).is_empty()); // get list of normal room is OK
-    /// # assert!(home.devices("Unknown room").is_empty()); // get list of unknown room is KO
+    /// # assert!(result.is_ok()); // normal add is OK
+    /// # assert!(home.add_device(room_name, Box::new(Socket::new())).is_err()); // duplicate add is KO
+    /// # assert!(home.add_device("Unknown room", Box::new(Socket::new())).is_err()) // add to the unknown room is KO
     /// ```
-    pub fn devices(&self, room_name: &str) -> Vec<String> {
-        let result: Vec<String> = self.manager_devices
-            .get_devices(room_name)
-            .cloned()
-            .collect();
-
-        result
+    pub fn add_device(&self, room_name: &str, device: Box<dyn Device>) -> Result<(), String> {
+        self.state_mut().manager_devices.add_device(room_name, device)
     }
 
-    /// Method print report about all devices of the home
+    /// Method add new device to the room and connect it to the parent device
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
     /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room_name = "Kitchen";
-    /// let room = Room::new(room_name);
-    /// home.add_room(room).unwrap();
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
     ///
-    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
-    /// home.add_device(room_name, Box::new(device)).unwrap();
+    /// let device = Thermometer::from("T01", "Description of T01");
+    /// let result = home.add_connected_device(room_name, "S01", Box::new(device));
     ///
-    /// home.print_report(); // prints information about only 1 device - Socket
+    /// # assert!(result.is_ok()); // normal add is OK
+    /// # assert_eq!(home.device_path("T01").unwrap(), vec!["Kitchen", "S01", "T01"]);
+    /// # assert!(home.add_connected_device(room_name, "S02", Box::new(Thermometer::new())).is_err()); // add to unknown parent is KO
     /// ```
-    pub fn print_report(&self) {
-        self.manager_devices.print_report();
+    pub fn add_connected_device(
+        &self,
+        room_name: &str,
+        parent_name: &str,
+        device: Box<dyn Device>,
+    ) -> Result<(), String> {
+        self.state_mut()
+            .manager_devices
+            .add_connected_device(room_name, parent_name, device)
     }
 
-    /// Method print schema connections of the home
+    /// Method set policy, which requires Connectable devices to be added only as connection
+    ///
+    /// When policy is on, `add_device` rejects Connectable devices (e.g. Thermometer)
+    /// and they must be added by `add_connected_device`. Policy is off by default
     ///
     /// Example:
     /// ```
     /// use crate::home::places::{Home, Room};
-    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
     ///
-    /// let mut home = Home::new("MY best Home");
+    /// let home = Home::new("MY best Home");
     /// let room_name = "Kitchen";
-    /// let room = Room::new(room_name);
-    /// home.add_room(room).unwrap();
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.set_require_connection_for_connectables(true);
     ///
-    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
-    /// home.add_device(room_name, Box::new(device)).unwrap();
+    /// let result = home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01")));
     ///
-    /// home.print_schema(); // prints room connected to the home and device connected to the home
+    /// # assert!(home.require_connection_for_connectables());
+    /// # assert!(result.is_err());
     /// ```
-    pub fn print_schema(&self) {
-        self.manager_devices.print_schema();
+    pub fn set_require_connection_for_connectables(&self, value: bool) {
+        self.state_mut()
+            .manager_devices
+            .set_require_connection_for_connectables(value);
+    }
+
+    /// Method return policy, which requires Connectable devices to be added only as connection
+    pub fn require_connection_for_connectables(&self) -> bool {
+        self.state().manager_devices.require_connection_for_connectables()
+    }
+
+    /// Method remove device from home
+    ///
+    /// Devices connected to the removed device stay in the room
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// let room = Room::new(room_name);
+    /// home.add_room(room).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "S01 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S02", "S02 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S03", "S03 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S04", "S04 Description", 1000.0))).unwrap();
+    ///
+    /// let result = home.remove_device("S03");
+    /// let devices = home.devices(room_name);
+    ///
+    /// # assert!(result.is_ok());
+    /// # assert!(!devices.contains(&String::from("S03")));
+    /// # assert_eq!(devices.len(), 3);
+    /// ```
+    pub fn remove_device(&self, device_name: &str) -> Result<(), String> {
+        self.state_mut().manager_devices.remove_device(device_name)
+    }
+
+    /// Method change name of the device
+    ///
+    /// Device stays in its room and keeps all its connections
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "S01 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S02", "S02 Description", 1000.0))).unwrap();
+    ///
+    /// let result = home.rename_device("S01", "Main socket");
+    ///
+    /// # assert!(result.is_ok()); // normal rename is OK
+    /// # assert_eq!(home.devices(room_name), vec!["Main socket", "S02"]);
+    /// # assert!(home.rename_device("S02", "Main socket").is_err()); // rename to existing name is KO
+    /// # assert!(home.rename_device("S01", "S03").is_err()); // rename of unknown device is KO
+    /// ```
+    pub fn rename_device(&self, old_name: &str, new_name: &str) -> Result<(), String> {
+        self.state_mut().manager_devices.rename_device(old_name, new_name)
+    }
+
+    /// Method remove device with all devices connected to it
+    ///
+    /// Unlike `remove_device`, which keeps connected devices in the room,
+    /// this method removes the whole subtree of connections.
+    /// Returns names of all removed devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "S01 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "T01 Description"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let removed = home.remove_device_cascade("S01").unwrap();
+    ///
+    /// # assert_eq!(removed, vec!["S01", "T01"]);
+    /// # assert!(home.devices(room_name).is_empty());
+    /// # assert!(home.remove_device_cascade("Unknown device").is_err());
+    /// ```
+    pub fn remove_device_cascade(&self, device_name: &str) -> Result<Vec<String>, String> {
+        self.state_mut().manager_devices.remove_device_cascade(device_name)
+    }
+
+    /// Method remove several devices from home
+    ///
+    /// Found devices are removed even if some names are not found.
+    /// All not found names are collected into the one error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "S01 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S02", "S02 Description", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S03", "S03 Description", 1000.0))).unwrap();
+    ///
+    /// let result = home.remove_devices(&["S01", "S03"]);
+    ///
+    /// # assert!(result.is_ok());
+    /// # assert_eq!(home.devices(room_name), vec!["S02"]);
+    /// ```
+    pub fn remove_devices(&self, device_names: &[&str]) -> Result<(), String> {
+        let mut state = self.state_mut();
+        let mut not_found = vec![];
+        for &device_name in device_names {
+            if state.manager_devices.remove_device(device_name).is_err() {
+                not_found.push(device_name);
+            }
+        }
+
+        if not_found.is_empty() {
+            Ok(())
+        } else {
+            let message = format!("Devices with names [{}] not found", not_found.join(", "));
+            Err(message)
+        }
+    }
+
+    /// Method import devices from CSV text
+    ///
+    /// Each line has format `room,kind,name,power`, where kind is `socket` or `thermometer`
+    /// and power is optional for thermometers. Rooms are created if needed.
+    /// Import is all-or-nothing: on any error the home stays unchanged.
+    /// Returns count of imported devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let csv = "Kitchen,socket,S01,1000\nKitchen,thermometer,T01";
+    ///
+    /// let result = home.import_csv(csv);
+    ///
+    /// # assert_eq!(result, Ok(2));
+    /// # assert_eq!(home.devices("Kitchen"), vec!["S01", "T01"]);
+    /// ```
+    pub fn import_csv(&self, csv: &str) -> Result<usize, String> {
+        let mut rows = vec![];
+        for (index, line) in csv.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = parse_csv_row(line)
+                .map_err(|error| format!("Line {}: {}", index + 1, error))?;
+            rows.push((index + 1, row));
+        }
+
+        let mut state = self.state_mut();
+        let mut staged_state = state.clone();
+        let mut added = 0;
+        for (line_number, (room_name, device)) in rows {
+            staged_state
+                .ensure_room(room_name.as_str())
+                .and_then(|_| staged_state.manager_devices.add_device(room_name.as_str(), device))
+                .map_err(|error| format!("Line {}: {}", line_number, error))?;
+            added += 1;
+        }
+
+        *state = staged_state;
+        Ok(added)
+    }
+
+    /// Method connects one device to another
+    ///
+    /// Connection must not exceed max depth of connections chain.
+    /// Device can have only one parent, use `reparent_device` to move connected device.
+    /// Returns true, if connection is new, and false, if devices already connected
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// let created = home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert!(created); // new connection is OK
+    /// # assert!(home.connect_device(room_name, "S01", "Unknown device").is_err()); // connect unknown device is KO
+    /// ```
+    pub fn connect_device(
+        &self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<bool, String> {
+        self.state_mut()
+            .manager_devices
+            .connect_device(room_name, device_connects_to, device_connected)
+    }
+
+    /// Method connects one device to another and return warnings about the connection
+    ///
+    /// Connection to socket, which is off, is created, but warning is returned,
+    /// because connected device may be unpowered
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// let outcome = home.connect_device_with_warnings(room_name, "S01", "T01").unwrap();
+    ///
+    /// for warning in outcome.warnings.iter() {
+    ///     println!("Warning: {}", warning);
+    /// }
+    ///
+    /// # assert!(outcome.created);
+    /// ```
+    pub fn connect_device_with_warnings(
+        &self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<ConnectOutcome, String> {
+        self.state_mut()
+            .manager_devices
+            .connect_device_with_warnings(room_name, device_connects_to, device_connected)
+    }
+
+    /// Method connects one device to another and return subtree of the parent device
+    ///
+    /// Subtree contains the new connection, so UI can refresh only this part of the schema
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// let node = home.connect_device_subtree(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(node.name, "S01");
+    /// # assert_eq!(node.children[0].name, "T01");
+    /// ```
+    pub fn connect_device_subtree(
+        &self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<SchemaNode, String> {
+        self.state_mut()
+            .manager_devices
+            .connect_device_subtree(room_name, device_connects_to, device_connected)
+    }
+
+    /// Method connects one device to another with label of the connection (e.g. "controls")
+    ///
+    /// Label is shown in schema and its JSON. If devices already connected, label is replaced.
+    /// Returns true, if connection is new, and false, if devices already connected
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// home.connect_device_labeled(room_name, "S01", "T01", "powers").unwrap();
+    ///
+    /// # assert_eq!(home.connection_label("S01", "T01").as_deref(), Some("powers"));
+    /// ```
+    pub fn connect_device_labeled(
+        &self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+        label: &str,
+    ) -> Result<bool, String> {
+        self.state_mut()
+            .manager_devices
+            .connect_device_labeled(room_name, device_connects_to, device_connected, label)
+    }
+
+    /// Method return label of connection between devices, if it was set
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(home.connection_label("S01", "T01"), None);
+    /// ```
+    pub fn connection_label(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Option<String> {
+        self.state()
+            .manager_devices
+            .connection_label(device_connects_to, device_connected)
+            .map(String::from)
+    }
+
+    /// Method check devices can be connected, without connecting them
+    ///
+    /// Connection is valid, if both devices exist in the same room,
+    /// connected device is Connectable and connection doesn't create cycle
+    /// or exceed max depth. Returns the same error, as connect_device would
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// if home.can_connect("S01", "T01").is_ok() {
+    ///     home.connect_device(room_name, "S01", "T01").unwrap();
+    /// }
+    ///
+    /// # assert!(home.can_connect("T01", "S01").is_err()); // socket is not connectable
+    /// ```
+    pub fn can_connect(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<(), String> {
+        self.state().manager_devices.can_connect(device_connects_to, device_connected)
+    }
+
+    /// Method connects device to device, which is placed in another room
+    ///
+    /// Unlike connect_device, devices may be placed in different rooms.
+    /// Such connection is marked as cross-room in schema and its JSON.
+    /// Returns true, if connection is new, and false, if devices already connected
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device("Hall", Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    ///
+    /// let created = home.connect_cross_room("Kitchen", "S01", "Hall", "T01").unwrap();
+    ///
+    /// # assert!(created);
+    /// # assert_eq!(home.device_children("S01"), vec!["T01"]);
+    /// ```
+    pub fn connect_cross_room(
+        &self,
+        parent_room: &str,
+        device_connects_to: &str,
+        child_room: &str,
+        device_connected: &str,
+    ) -> Result<bool, String> {
+        self.state_mut().manager_devices.connect_cross_room(
+            parent_room,
+            device_connects_to,
+            child_room,
+            device_connected,
+        )
+    }
+
+    /// Method connect all pairs (device_connects_to, device_connected) as one operation
+    ///
+    /// Useful to load topology of connections. As in connect_device, devices of each
+    /// connection must be placed in the same room.
+    /// If any connection is not valid (unknown device, other room, cycle, second parent) -
+    /// return Error and no connection of the batch is applied
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device("Kitchen", Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.add_device("Kitchen", Box::new(Thermometer::from("T02", "Description of T02"))).unwrap();
+    /// home.add_device("Hall", Box::new(Thermometer::from("T03", "Description of T03"))).unwrap();
+    ///
+    /// home.connect_devices_batch(&[("S01", "T01"), ("S01", "T02")]).unwrap();
+    ///
+    /// # assert_eq!(home.device_children("S01"), vec!["T01", "T02"]);
+    /// # assert!(home.connect_devices_batch(&[("S01", "Unknown")]).is_err());
+    /// # assert!(home.connect_devices_batch(&[("T01", "T03")]).is_err()); // other room
+    /// ```
+    pub fn connect_devices_batch(&self, edges: &[(&str, &str)]) -> Result<(), String> {
+        self.state_mut().manager_devices.connect_devices_batch(edges)
+    }
+
+    /// Method disconnect device from parent device
+    ///
+    /// Disconnected device stays in its room.
+    /// If devices are not connected - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let result = home.disconnect_device("S01", "T01");
+    ///
+    /// # assert!(result.is_ok()); // normal disconnect is OK
+    /// # assert!(home.disconnect_device("S01", "T01").is_err()); // disconnect twice is KO
+    /// ```
+    pub fn disconnect_device(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<(), String> {
+        self.state_mut()
+            .manager_devices
+            .disconnect_device(device_connects_to, device_connected)
+    }
+
+    /// Method move connected device under another parent device
+    ///
+    /// Both devices must be in the same room and move must not create a cycle
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("S02", "Description of S02", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let result = home.reparent_device("T01", "S02");
+    ///
+    /// # assert!(result.is_ok()); // normal reparent is OK
+    /// # assert!(home.reparent_device("T01", "Unknown device").is_err()); // reparent to unknown device is KO
+    /// ```
+    pub fn reparent_device(&self, device_name: &str, new_parent: &str) -> Result<(), String> {
+        self.state_mut().manager_devices.reparent_device(device_name, new_parent)
+    }
+
+    /// Method set max depth of connections chain
+    ///
+    /// Connection, which makes chain deeper than max depth, is rejected.
+    /// Default value is DEFAULT_MAX_DEPTH
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    /// use crate::home::services::DEFAULT_MAX_DEPTH;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// # assert_eq!(home.max_depth(), DEFAULT_MAX_DEPTH);
+    ///
+    /// home.set_max_depth(4);
+    /// # assert_eq!(home.max_depth(), 4);
+    /// ```
+    pub fn set_max_depth(&self, max_depth: usize) {
+        self.state_mut().manager_devices.set_max_depth(max_depth);
+    }
+
+    /// Method return max depth of connections chain
+    pub fn max_depth(&self) -> usize {
+        self.state().manager_devices.max_depth()
+    }
+
+    /// Method set max count of devices connected directly to one device
+    ///
+    /// Connection, which exceeds the limit of the parent, is rejected.
+    /// None means unlimited, it is default value
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Home;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// # assert_eq!(home.max_children(), None);
+    ///
+    /// home.set_max_children(Some(2));
+    /// # assert_eq!(home.max_children(), Some(2));
+    /// ```
+    pub fn set_max_children(&self, max_children: Option<usize>) {
+        self.state_mut().manager_devices.set_max_children(max_children);
+    }
+
+    /// Method return max count of devices connected directly to one device
+    pub fn max_children(&self) -> Option<usize> {
+        self.state().manager_devices.max_children()
+    }
+
+    /// Method find device by its name
+    ///
+    /// Copy of the device is returned, so changes of the home are not visible through it
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// let room = Room::new(room_name);
+    /// home.add_room(room).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// let device_opt = home.device("Socket");
+    /// match device_opt {
+    ///     Some(device_found) => println!("Found device with name {}", device_found.name()),
+    ///     None => panic!("Not found device!")
+    /// }
+    ///
+    /// # assert!(home.device("Socket").is_some()); // normal find is OK
+    /// # assert!(home.device("Unknown device").is_none()); // find of unknown device is KO
+    /// ```
+    pub fn device(&self, device_name: &str) -> Option<Box<dyn Device>> {
+        self.state().manager_devices.get_device(device_name).map(|device| device.clone_box())
+    }
+
+    /// Method return identifier of the device
+    ///
+    /// Identifier is not changed by renaming or by removing another devices,
+    /// so device can be accessed by it without search by name
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// let id = home.device_id("Socket").unwrap();
+    ///
+    /// # assert_eq!(home.device_by_id(id).unwrap().name(), "Socket");
+    /// ```
+    pub fn device_id(&self, device_name: &str) -> Option<DeviceId> {
+        self.state().manager_devices.device_id(device_name)
+    }
+
+    /// Method return device by its identifier
+    pub fn device_by_id(&self, id: DeviceId) -> Option<Box<dyn Device>> {
+        self.state().manager_devices.get_device_by_id(id).map(|device| device.clone_box())
+    }
+
+    /// Method remove device by its identifier, as `remove_device` does
+    pub fn remove_device_by_id(&self, id: DeviceId) -> Result<(), String> {
+        self.state_mut().manager_devices.remove_device_by_id(id)
+    }
+
+    /// Method return owned snapshot of the device state
+    ///
+    /// Unlike `device`, snapshot contains plain data and can be moved to another thread
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// let snapshot = home.device_snapshot("Socket").unwrap();
+    ///
+    /// # assert_eq!(snapshot.enabled, Some(false));
+    /// # assert!(home.device_snapshot("Unknown device").is_none());
+    /// ```
+    pub fn device_snapshot(&self, device_name: &str) -> Option<DeviceSnapshot> {
+        let state = self.state();
+        state.manager_devices.get_device(device_name).map(DeviceSnapshot::of)
+    }
+
+    /// Method return device by its name or error, if device not found
+    ///
+    /// Copy of the device is returned, as in `device`
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// let status = home.require_device("Socket").map(|device| device.status());
+    ///
+    /// # assert_eq!(status, Ok(String::from("OK")));
+    /// # assert!(home.require_device("Unknown device").is_err());
+    /// ```
+    pub fn require_device(&self, device_name: &str) -> Result<Box<dyn Device>, String> {
+        self.state().manager_devices.require_device(device_name).map(|device| device.clone_box())
+    }
+
+    /// Method return devices directly connected to the device
+    ///
+    /// Only connections are listed, devices placed in the room are not children of each other.
+    /// For unknown device - return empty list
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// for child in home.device_children("S01") {
+    ///     println!("{}", child);
+    /// }
+    ///
+    /// # assert_eq!(home.device_children("S01"), vec!["T01"]);
+    /// # assert!(home.device_children("Unknown device").is_empty());
+    /// ```
+    pub fn device_children(&self, device_name: &str) -> Vec<String> {
+        self.state().manager_devices.device_children(device_name)
+    }
+
+    /// Method return all devices connected to the device directly or through another devices
+    ///
+    /// Devices are returned depth-first, children of each device in order of connection.
+    /// For unknown device - return empty list
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T02", "Description of T02"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T02").unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(home.connected_devices_ordered("S01"), vec!["T02", "T01"]);
+    /// ```
+    pub fn connected_devices_ordered(&self, device_name: &str) -> Vec<String> {
+        self.state().manager_devices.descendants(device_name)
+    }
+
+    /// Method return tree of devices connected to the device
+    ///
+    /// Return None, if home not contains device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    ///
+    /// let node = home.subtree("S01").unwrap();
+    /// # assert!(node.children.is_empty());
+    /// ```
+    pub fn subtree(&self, device_name: &str) -> Option<SchemaNode> {
+        self.state().manager_devices.subtree(device_name)
+    }
+
+    /// Method return path from the room through parent devices to the device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let path = home.device_path("T01").unwrap();
+    /// println!("{}", path.join(" -> "));
+    ///
+    /// # assert_eq!(path, vec!["Kitchen", "S01", "T01"]);
+    /// # assert!(home.device_path("Unknown device").is_none());
+    /// ```
+    pub fn device_path(&self, device_name: &str) -> Option<Vec<String>> {
+        self.state().manager_devices.device_path(device_name)
+    }
+
+    /// Method turn on or turn off all sockets of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// home.set_all_sockets(true);
+    ///
+    /// # assert_eq!(home.summary_stats().enabled_sockets, 1);
+    /// ```
+    pub fn set_all_sockets(&self, enabled: bool) {
+        let mut state = self.state_mut();
+        for device in state.manager_devices.all_devices_mut() {
+            if let Some(socket) = device.as_socket_mut() {
+                if enabled {
+                    socket.power_on();
+                } else {
+                    socket._power_off();
+                }
+            }
+        }
+    }
+
+    /// Method return names of devices, which contain query, case is ignored
+    ///
+    /// Devices are returned in order of adding
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Kettle", "Description of Kettle", 1000.0))).unwrap();
+    ///
+    /// # assert_eq!(home.find_devices("KET"), vec!["Kettle"]);
+    /// # assert!(home.find_devices("lamp").is_empty());
+    /// ```
+    pub fn find_devices(&self, query: &str) -> Vec<String> {
+        let query = query.to_lowercase();
+        self.state()
+            .manager_devices
+            .all_devices()
+            .map(|device| device.name())
+            .filter(|name| name.to_lowercase().contains(query.as_str()))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Method return names of sockets, which power consumption exceeds their max power
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    ///
+    /// for socket_name in home.overloaded_sockets() {
+    ///     println!("Socket {} is overloaded", socket_name);
+    /// }
+    ///
+    /// # assert!(home.overloaded_sockets().is_empty());
+    /// ```
+    pub fn overloaded_sockets(&self) -> Vec<String> {
+        self.state()
+            .manager_devices
+            .all_devices()
+            .filter_map(|device| device.as_socket())
+            .filter(|socket| socket.is_overloaded())
+            .map(|socket| String::from(socket.name()))
+            .collect()
+    }
+
+    /// Method return list of all devices names in the specific room
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// let room = Room::new(room_name);
+    /// home.add_room(room).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// let devices_names = home.devices(room_name);
+    ///
+    /// for device_name in devices_names {
+    ///     println!("{}", device_name);
+    /// }
+    ///
+    /// # assert!(!home.devices(room_name).is_empty()); // get list of normal room is OK
+    /// # assert!(home.devices("Unknown room").is_empty()); // get list of unknown room is KO
+    /// ```
+    pub fn devices(&self, room_name: &str) -> Vec<String> {
+        let result: Vec<String> = self
+            .state()
+            .manager_devices
+            .get_devices(room_name)
+            .cloned()
+            .collect();
+
+        result
+    }
+
+    /// Method return names of devices of the specific kind placed in the room
+    ///
+    /// Connected devices are not included, as in `devices`
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::DeviceKind;
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    ///
+    /// let sockets = home.room_devices_by_kind("Kitchen", DeviceKind::Socket);
+    ///
+    /// # assert_eq!(sockets, vec!["S01"]);
+    /// # assert!(home.room_devices_by_kind("Kitchen", DeviceKind::Thermometer).is_empty());
+    /// ```
+    pub fn room_devices_by_kind(&self, room_name: &str, kind: DeviceKind) -> Vec<String> {
+        let state = self.state();
+        state
+            .manager_devices
+            .get_devices(room_name)
+            .filter(|device_name| {
+                state
+                    .manager_devices
+                    .get_device(device_name)
+                    .is_some_and(|device| device.kind() == kind)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Method return list of all devices names in the specific room
+    ///
+    /// Unlike `devices`, unknown room is reported as error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// match home.try_devices(room_name) {
+    ///     Ok(devices_names) => println!("Room contains {} devices", devices_names.len()),
+    ///     Err(error) => println!("{}", error),
+    /// }
+    ///
+    /// # assert!(home.try_devices(room_name).unwrap().is_empty()); // get list of empty room is OK
+    /// # assert!(home.try_devices("Unknown room").is_err()); // get list of unknown room is KO
+    /// ```
+    pub fn try_devices(&self, room_name: &str) -> Result<Vec<String>, String> {
+        let state = self.state();
+        state.require_room(room_name)?;
+
+        Ok(state.manager_devices.get_devices(room_name).cloned().collect())
+    }
+
+    /// Method pass devices names of the specific room to the closure without cloning them
+    ///
+    /// Closure is called with read lock of the home, so it must not change the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    /// home.add_device(room_name, Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// home.with_devices(room_name, |devices| {
+    ///     for device_name in devices {
+    ///         println!("{}", device_name);
+    ///     }
+    /// });
+    ///
+    /// # assert_eq!(home.with_devices(room_name, |devices| devices.map(String::from).collect::<Vec<String>>()), vec!["Socket"]);
+    /// # assert_eq!(home.with_devices("Unknown room", |devices| devices.count()), 0);
+    /// ```
+    pub fn with_devices<R>(
+        &self,
+        room_name: &str,
+        f: impl FnOnce(Box<dyn Iterator<Item = &str> + '_>) -> R,
+    ) -> R {
+        let state = self.state();
+        f(Box::new(state.manager_devices.get_devices(room_name).map(String::as_str)))
+    }
+
+    /// Method return list of all devices names in the specific room including connected devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// home.add_device(room_name, Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device(room_name, Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// for device_name in home.room_devices_recursive(room_name) {
+    ///     println!("{}", device_name);
+    /// }
+    ///
+    /// # assert_eq!(home.room_devices_recursive(room_name), vec!["S01", "T01"]);
+    /// # assert!(home.room_devices_recursive("Unknown room").is_empty());
+    /// ```
+    pub fn room_devices_recursive(&self, room_name: &str) -> Vec<String> {
+        self.state().manager_devices.get_devices_recursive(room_name)
+    }
+
+    /// Method return summary statistics of the home
+    ///
+    /// Statistics are computed in one pass over the devices store
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// let stats = home.summary_stats();
+    /// println!("Home contains {} devices", stats.devices);
+    ///
+    /// # assert_eq!(stats.rooms, 1);
+    /// # assert_eq!(stats.sockets, 1);
+    /// ```
+    pub fn summary_stats(&self) -> HomeStats {
+        let state = self.state();
+        let mut stats = HomeStats {
+            rooms: state.rooms.len(),
+            devices: 0,
+            sockets: 0,
+            thermometers: 0,
+            total_power: 0.0,
+            enabled_sockets: 0,
+        };
+
+        for device in state.manager_devices.all_devices() {
+            stats.devices += 1;
+            if device.kind() == DeviceKind::Thermometer {
+                stats.thermometers += 1;
+            }
+
+            let sockets: &[Socket] = match (device.as_socket(), device.as_power_strip()) {
+                (Some(socket), _) => std::slice::from_ref(socket),
+                (_, Some(strip)) => strip.sockets(),
+                _ => &[],
+            };
+            for socket in sockets {
+                stats.sockets += 1;
+                if socket.is_enabled() {
+                    stats.enabled_sockets += 1;
+                    stats.total_power += socket.power_consumption();
+                }
+            }
+        }
+
+        stats
+    }
+
+    /// Method return summary power consumption of enabled sockets for each room
+    ///
+    /// Sockets connected to devices of the room and sockets of power strips are included
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let mut socket = Socket::from("S01", "Description of S01", 1000.0);
+    /// socket.set_power_consumption(300.0).unwrap();
+    /// socket.power_on();
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_device("Kitchen", Box::new(socket)).unwrap();
+    ///
+    /// for (room_name, power) in home.power_report() {
+    ///     println!("{}: {}", room_name, power);
+    /// }
+    ///
+    /// # assert_eq!(
+    /// #     home.power_report(),
+    /// #     vec![(String::from("Kitchen"), 300.0), (String::from("Hall"), 0.0)]
+    /// # );
+    /// ```
+    pub fn power_report(&self) -> Vec<(String, f32)> {
+        let state = self.state();
+        state
+            .rooms
+            .iter()
+            .map(|room| (String::from(room.name()), state.room_power(room.name())))
+            .collect()
+    }
+
+    /// Method return report about devices of one room
+    ///
+    /// Report contains name of the room, info of each device placed in the room
+    /// (including connected ones) and total power consumption of the room.
+    /// Devices of other rooms, connected to devices of the room, are not included.
+    /// If room not found - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_room(Room::new("Hall")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device("Hall", Box::new(Socket::from("S02", "Description of S02", 1000.0))).unwrap();
+    ///
+    /// let report = home.room_report("Kitchen").unwrap();
+    ///
+    /// # assert!(report.contains("[SOCKET] S01"));
+    /// # assert!(!report.contains("[SOCKET] S02"));
+    /// # assert!(home.room_report("Unknown room").is_err());
+    /// ```
+    pub fn room_report(&self, room_name: &str) -> Result<String, String> {
+        let state = self.state();
+        state.require_room(room_name)?;
+
+        let mut report = format!("Generated report about room [{}]:\n", room_name);
+        for device_name in state.placed_devices_recursive(room_name) {
+            if let Some(device) = state.manager_devices.get_device(device_name.as_str()) {
+                report.push_str(format!("{}\n\n", device.info()).as_str());
+            }
+        }
+        report.push_str(format!("Total power: {}\n", state.room_power(room_name)).as_str());
+
+        Ok(report)
+    }
+
+    /// Method return number of all devices of the home, including connected ones
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device("Kitchen", Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device("Kitchen", "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(home.total_devices(), 2);
+    /// ```
+    pub fn total_devices(&self) -> usize {
+        self.state().manager_devices.all_devices().len()
+    }
+
+    /// Method return statistics of connections of the home
+    ///
+    /// Helps to find over-connected devices and too long connection chains
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    ///
+    /// let stats = home.schema_stats();
+    ///
+    /// # assert_eq!((stats.rooms, stats.nodes, stats.edges), (1, 1, 0));
+    /// ```
+    pub fn schema_stats(&self) -> SchemaStats {
+        self.state().manager_devices.schema_stats()
+    }
+
+    /// Method return each node of the home (root, room or device) with its direct children
+    ///
+    /// Root goes first, then rooms and then devices room by room, all in order of adding
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    ///
+    /// for (node, children) in home.adjacency_list() {
+    ///     println!("{} -> {:?}", node, children);
+    /// }
+    ///
+    /// # assert_eq!(home.adjacency_list().len(), 3);
+    /// ```
+    pub fn adjacency_list(&self) -> Vec<(String, Vec<String>)> {
+        self.state().manager_devices.adjacency_list()
+    }
+
+    /// Method return number of devices, which are not connected to another device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("S01", "Description of S01", 1000.0))).unwrap();
+    /// home.add_device("Kitchen", Box::new(Thermometer::from("T01", "Description of T01"))).unwrap();
+    /// home.connect_device("Kitchen", "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(home.top_level_devices(), 1);
+    /// ```
+    pub fn top_level_devices(&self) -> usize {
+        self.state().manager_devices.top_level_devices().len()
+    }
+
+    /// Method check consistency of the home stores
+    ///
+    /// Returns list of found inconsistencies, empty for a healthy home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// home.add_room(Room::new(room_name)).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// for inconsistency in home.validate() {
+    ///     println!("{:?}", inconsistency);
+    /// }
+    ///
+    /// # assert!(home.validate().is_empty());
+    /// ```
+    pub fn validate(&self) -> Vec<Inconsistency> {
+        self.state().manager_devices.validate()
+    }
+
+    /// Method save copy of current rooms and devices of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "", 1000.0))).unwrap();
+    ///
+    /// let memento = home.take_snapshot();
+    /// home.remove_device("Socket").unwrap();
+    ///
+    /// # assert!(home.device("Socket").is_none());
+    /// home.restore_snapshot(memento);
+    /// # assert!(home.device("Socket").is_some());
+    /// ```
+    pub fn take_snapshot(&self) -> HomeMemento {
+        HomeMemento {
+            state: self.state().clone(),
+        }
+    }
+
+    /// Method replace current rooms and devices of the home by saved ones
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let memento = home.take_snapshot();
+    ///
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.restore_snapshot(memento);
+    ///
+    /// # assert!(home.rooms().is_empty());
+    /// ```
+    pub fn restore_snapshot(&self, memento: HomeMemento) {
+        *self.state_mut() = memento.state;
+    }
+
+    /// Test hook to access services of the home
+    #[cfg(test)]
+    pub(crate) fn manager_devices_mut(&mut self) -> &mut ServiceDeviceManagement {
+        let state = self.state.get_mut().unwrap_or_else(PoisonError::into_inner);
+        &mut state.manager_devices
+    }
+
+    /// Method lock the home for reading
+    ///
+    /// If other thread panicked with the lock, state is still used: each change of the home
+    /// leaves stores consistent or returns error before changing them
+    fn state(&self) -> RwLockReadGuard<'_, HomeState> {
+        self.state.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Method lock the home for changing
+    fn state_mut(&self) -> RwLockWriteGuard<'_, HomeState> {
+        self.state.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Method print report about all devices of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// let room = Room::new(room_name);
+    /// home.add_room(room).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// home.print_report(); // prints information about only 1 device - Socket
+    /// ```
+    pub fn print_report(&self) {
+        self.state().manager_devices.print_report();
+    }
+
+    /// Method return report about all devices of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// let report = home.collect_report();
+    ///
+    /// # assert!(report.contains("[SOCKET] Socket"));
+    /// ```
+    pub fn collect_report(&self) -> String {
+        self.state().manager_devices.collect_report()
+    }
+
+    /// Method write report about all devices of the home to the writer (file, socket, buffer)
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+    ///
+    /// home.write_report(&mut std::io::stdout()).unwrap();
+    /// ```
+    pub fn write_report<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.state().manager_devices.write_report(writer)
+    }
+
+    /// Method write schema connections of the home to the writer (file, socket, buffer)
+    ///
+    /// Output is the same as `collect_schema`
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    ///
+    /// home.write_schema(&mut std::io::stdout()).unwrap();
+    /// ```
+    pub fn write_schema<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let format = SchemaFormat::default();
+        self.state().manager_devices.write_schema(writer, self.name.as_str(), &format)
+    }
+
+    /// Method return JSON of rooms and device links of the home
+    ///
+    /// Device parameters are not included, only topology of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "", 1000.0))).unwrap();
+    ///
+    /// let json = home.schema_json();
+    /// # assert_eq!(json, r#"{"rooms":[{"name":"Kitchen","devices":["Socket"]}],"links":[]}"#);
+    /// ```
+    pub fn schema_json(&self) -> String {
+        self.state().manager_devices.schema_json()
+    }
+
+    /// Method return JSON of rooms and device links of the home in requested format
+    ///
+    /// Pretty format puts every room and link on its own line,
+    /// compact format is the same as `schema_json`
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::services::JsonFormat;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "", 1000.0))).unwrap();
+    ///
+    /// let json = home.schema_json_with(JsonFormat::Compact);
+    /// # assert_eq!(json, home.schema_json());
+    /// ```
+    pub fn schema_json_with(&self, format: JsonFormat) -> String {
+        self.state().manager_devices.schema_json_with(format)
+    }
+
+    /// Method print schema connections of the home
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// let room_name = "Kitchen";
+    /// let room = Room::new(room_name);
+    /// home.add_room(room).unwrap();
+    ///
+    /// let device = Socket::from("Socket", "Description of Socket", 1000.0);
+    /// home.add_device(room_name, Box::new(device)).unwrap();
+    ///
+    /// home.print_schema(); // prints room connected to the home and device connected to the home
+    /// ```
+    pub fn print_schema(&self) {
+        print!("{}", self.collect_schema());
+    }
+
+    /// Method return schema connections of the home as text
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::devices::socket::Socket;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    /// home.add_device("Kitchen", Box::new(Socket::from("Socket", "", 1000.0))).unwrap();
+    ///
+    /// let schema = home.collect_schema();
+    ///
+    /// # assert!(schema.starts_with("[HOME] MY best Home\n"));
+    /// ```
+    pub fn collect_schema(&self) -> String {
+        self.state().manager_devices.collect_schema(self.name.as_str())
+    }
+
+    /// Method return schema connections of the home as text with custom indentation and bullet
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::{Home, Room};
+    /// use crate::home::services::SchemaFormat;
+    ///
+    /// let home = Home::new("MY best Home");
+    /// home.add_room(Room::new("Kitchen")).unwrap();
+    ///
+    /// let format = SchemaFormat { indent: String::from("  "), bullet: String::from("*") };
+    /// let schema = home.collect_schema_with(&format);
+    ///
+    /// # assert_eq!(schema, "[HOME] MY best Home\n* [ROOM] Kitchen\n");
+    /// # assert_eq!(home.collect_schema_with(&SchemaFormat::default()), home.collect_schema());
+    /// ```
+    pub fn collect_schema_with(&self, format: &SchemaFormat) -> String {
+        self.state().manager_devices.collect_schema_with(self.name.as_str(), format)
+    }
+}
+
+/// Function parse CSV line `room,kind,name,power` to room name and device
+fn parse_csv_row(line: &str) -> Result<(String, Box<dyn Device>), String> {
+    let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+    if fields.len() < 3 || fields.len() > 4 {
+        let message = format!("Expected 3 or 4 fields, found {}", fields.len());
+        return Err(message);
+    }
+
+    let (room_name, kind, name) = (fields[0], fields[1], fields[2]);
+    let power = fields.get(3).filter(|power| !power.is_empty());
+
+    let device: Box<dyn Device> = match kind.parse::<DeviceKind>()? {
+        DeviceKind::Socket => match power {
+            Some(power) => match power.parse::<f32>() {
+                Ok(power) => Box::new(Socket::from(name, "", power)),
+                Err(_) => return Err(format!("Invalid power [{}]", power)),
+            },
+            None => return Err(format!("Power is required for socket [{}]", name)),
+        },
+        DeviceKind::Thermometer => Box::new(Thermometer::from(name, "")),
+        DeviceKind::PowerStrip => Box::new(PowerStrip::from(name, "")),
+        DeviceKind::Other => return Err(format!("Unknown device kind [{}]", kind)),
+    };
+
+    Ok((String::from(room_name), device))
+}
+
+/// Iterate over snapshots of all devices of the home
+///
+/// Snapshots of all devices are built under one read lock before iteration starts,
+/// so each iteration allocates them at once, and changes of the home made during
+/// iteration are not visible. Use `devices` or `with_devices` to get only names
+///
+/// Example:
+/// ```
+/// use crate::home::places::{Home, Room};
+/// use crate::home::devices::socket::Socket;
+///
+/// let home = Home::new("MY best Home");
+/// let room_name = "Kitchen";
+/// home.add_room(Room::new(room_name)).unwrap();
+/// home.add_device(room_name, Box::new(Socket::from("Socket", "Description of Socket", 1000.0))).unwrap();
+///
+/// for device in &home {
+///     println!("{}", device.name);
+/// }
+///
+/// # assert_eq!((&home).into_iter().count(), 1);
+/// ```
+impl IntoIterator for &Home {
+    type Item = DeviceSnapshot;
+    type IntoIter = std::vec::IntoIter<DeviceSnapshot>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let state = self.state();
+        let snapshots: Vec<DeviceSnapshot> = state
+            .manager_devices
+            .all_devices()
+            .map(|device| DeviceSnapshot::of(device.as_ref()))
+            .collect();
+        snapshots.into_iter()
     }
 }
 
 /// Struct to store Room information
 ///
 /// Struct contains only name
+#[derive(Clone)]
 pub struct Room {
     name: String,
 }
@@ -1,19 +1,105 @@
-use crate::stores::HOME_NAME;
 use crate::{
-    devices::Device,
+    devices::{power_strip, socket, thermometer, Device},
     places::Room,
-    stores::{StoreDeviceLinks, StoreDevices},
+    stores::{DeviceId, LinkKind, StoreDeviceLinks, StoreDevices},
 };
-use std::slice::Iter;
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    slice::Iter,
+    vec::IntoIter,
+};
+
+/// Default max depth of connections chain
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Beginning of error, which is returned on adding second device with default name
+pub const DEFAULT_NAME_TAKEN: &str = "Devices store already contains device with default name";
+
+/// Inconsistency between devices store and schema store
+#[derive(Debug, PartialEq)]
+pub enum Inconsistency {
+    /// Device contains in devices store, but not placed in any room
+    OrphanedDevice(String),
+    /// Schema contains device, which not contains in devices store
+    DanglingLink(String),
+    /// Room lists device, which not contains in devices store
+    MissingRoomDevice { room: String, device: String },
+}
+
+/// Layout of JSON produced by the schema export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonFormat {
+    /// Human-readable JSON, one room or link per line
+    Pretty,
+    /// JSON without any whitespace
+    Compact,
+}
+
+/// Device of the schema with devices connected to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaNode {
+    pub name: String,
+    /// Connected devices in order of connection
+    pub children: Vec<SchemaNode>,
+}
+
+/// Indentation and bullet of schema text
+///
+/// Default format is tab indentation with `-` bullet
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaFormat {
+    /// Indentation of one level of the schema
+    pub indent: String,
+    /// Mark before each room or device
+    pub bullet: String,
+}
+
+impl Default for SchemaFormat {
+    fn default() -> Self {
+        SchemaFormat {
+            indent: String::from("\t"),
+            bullet: String::from("-"),
+        }
+    }
+}
+
+/// Statistics of the schema connections
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaStats {
+    /// Count of devices in the schema
+    pub nodes: usize,
+    /// Count of connections between devices
+    pub edges: usize,
+    /// Length of the longest connections chain
+    pub max_depth: usize,
+    /// Max count of devices connected directly to one device
+    pub max_fanout: usize,
+    /// Count of rooms in the schema
+    pub rooms: usize,
+}
+
+/// Result of successful connection of devices
+#[derive(Debug, PartialEq)]
+pub struct ConnectOutcome {
+    /// True, if connection is new
+    pub created: bool,
+    /// Non-fatal problems of the connection (e.g. parent socket is off)
+    pub warnings: Vec<String>,
+}
 
 /// Struct to store services to manage home
 ///
 /// Struct contains two services:
 /// 1. Service which manage devices store
 /// 2. Service which manage schema of the home
+///
+/// Also struct contains policies of the home
+#[derive(Clone)]
 pub struct ServiceDeviceManagement {
     service_devices: ServiceDevices,
     service_schema: ServiceSchemaDevices,
+    require_connection_for_connectables: bool,
 }
 
 impl Default for ServiceDeviceManagement {
@@ -37,6 +123,25 @@ impl ServiceDeviceManagement {
         ServiceDeviceManagement {
             service_devices,
             service_schema,
+            require_connection_for_connectables: false,
+        }
+    }
+
+    /// Method create new struct with preallocated stores
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let service = ServiceDeviceManagement::with_capacity(10, 100);
+    /// ```
+    pub fn with_capacity(rooms: usize, devices: usize) -> Self {
+        let service_devices = ServiceDevices::with_capacity(devices);
+        let service_schema = ServiceSchemaDevices::with_capacity(rooms, devices);
+        ServiceDeviceManagement {
+            service_devices,
+            service_schema,
+            require_connection_for_connectables: false,
         }
     }
 
@@ -62,6 +167,8 @@ impl ServiceDeviceManagement {
 
     /// Method remove room from schema
     ///
+    /// All devices of the room are removed from both services, their names are returned
+    ///
     /// Example:
     /// ```
     /// use crate::home::places::Room;
@@ -71,18 +178,64 @@ impl ServiceDeviceManagement {
     /// let room = Room::new(room_name);
     /// let mut service = ServiceDeviceManagement::new();
     /// let result_add: Result<(), String> = service.add_room(&room);
-    /// let result_remove: Result<(), String> = service.remove_room(room_name);
+    /// let result_remove: Result<Vec<String>, String> = service.remove_room(room_name);
     ///
     /// # assert!(result_add.is_ok()); // normal add is OK
     /// # assert!(result_remove.is_ok()); // normal remove is OK
     /// # assert!(service.remove_room(room_name).is_err()); // remove room, that not contains, is KO
     /// ```
-    pub fn remove_room(&mut self, room_name: &str) -> Result<(), String> {
-        self.service_schema.remove_room(room_name)
+    pub fn remove_room(&mut self, room_name: &str) -> Result<Vec<String>, String> {
+        if !self.service_schema.contains_room(room_name) {
+            let message = format!("Room [{}] not contains in home", room_name);
+            return Err(message);
+        }
+
+        let devices: Vec<String> = self.get_devices(room_name).cloned().collect();
+        for device_name in devices.iter() {
+            self.remove_device(device_name)?;
+        }
+        self.service_schema.remove_room(room_name)?;
+
+        Ok(devices)
+    }
+
+    /// Method move room to new position in the schema
+    ///
+    /// New position is clamped to bounds of rooms list
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new("Kitchen")).unwrap();
+    /// service.add_room(&Room::new("Hall")).unwrap();
+    ///
+    /// # assert!(service.reorder_room("Hall", 0).is_ok());
+    /// # assert!(service.reorder_room("Unknown", 0).is_err());
+    /// ```
+    pub fn reorder_room(&mut self, room_name: &str, new_index: usize) -> Result<(), String> {
+        self.service_schema.reorder_room(room_name, new_index)
+    }
+
+    /// Method set policy, which requires Connectable devices to be added only as connection
+    ///
+    /// Policy is off by default
+    pub fn set_require_connection_for_connectables(&mut self, value: bool) {
+        self.require_connection_for_connectables = value;
+    }
+
+    /// Method return policy, which requires Connectable devices to be added only as connection
+    pub fn require_connection_for_connectables(&self) -> bool {
+        self.require_connection_for_connectables
     }
 
     /// Method add device to specific room to services
     ///
+    /// If policy `require_connection_for_connectables` is on,
+    /// Connectable device can be added only by `add_connected_device`
+    ///
     /// Example:
     /// ```
     /// use crate::home::devices::socket::Socket;
@@ -100,10 +253,70 @@ impl ServiceDeviceManagement {
     /// # assert!(service.add_device(room.name(), Box::new(Socket::new())).is_err()); // duplicate add is KO
     /// ```
     pub fn add_device(&mut self, room_name: &str, device: Box<dyn Device>) -> Result<(), String> {
+        if self.require_connection_for_connectables && device.is_connectable() {
+            let message = format!(
+                "Device [{}] is Connectable and must be connected to another device",
+                device.name()
+            );
+            return Err(message);
+        }
+
+        self.insert_device(room_name, device)
+    }
+
+    /// Method add device to specific room and connect it to the parent device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.set_require_connection_for_connectables(true);
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// let device = Thermometer::from("T01", "Description");
+    /// let result = service.add_connected_device(room.name(), "S01", Box::new(device));
+    ///
+    /// # assert!(result.is_ok()); // normal add is OK
+    /// # assert_eq!(service.device_path("T01").unwrap(), vec!["My room", "S01", "T01"]);
+    /// ```
+    pub fn add_connected_device(
+        &mut self,
+        room_name: &str,
+        parent_name: &str,
+        device: Box<dyn Device>,
+    ) -> Result<(), String> {
+        if !self.service_schema.contains_device_in_room(room_name, parent_name) {
+            let message = format!("Room[{}] not contains device[{}]", room_name, parent_name);
+            return Err(message);
+        }
+
+        let device_name = String::from(device.name());
+        self.insert_device(room_name, device)?;
+        if let Err(error) = self
+            .service_schema
+            .connect_device(room_name, parent_name, device_name.as_str())
+        {
+            self.remove_device(device_name.as_str())?;
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Method add device to both stores without checking policies
+    fn insert_device(&mut self, room_name: &str, device: Box<dyn Device>) -> Result<(), String> {
         let device_name = String::from(device.name());
         self.service_devices.add_device(device)?;
-        self.service_schema
-            .add_device(room_name, device_name.as_str())?;
+        if let Err(error) = self.service_schema.add_device(room_name, device_name.as_str()) {
+            self.service_devices.remove_device(device_name.as_str())?;
+            return Err(error);
+        }
         Ok(())
     }
 
@@ -112,50 +325,1007 @@ impl ServiceDeviceManagement {
         self.service_devices.remove_device(device_name)
     }
 
+    /// Method change name of the device keeping its room and connections
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// let result = service.rename_device("S01", "S02");
+    ///
+    /// # assert!(result.is_ok()); // normal rename is OK
+    /// # assert!(service.get_device("S02").is_some());
+    /// # assert!(service.rename_device("S01", "S03").is_err()); // rename of unknown device is KO
+    /// ```
+    pub fn rename_device(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        if self.get_device(old_name).is_none() || !self.service_schema.contains_device(old_name) {
+            let message = format!("Home not contains device[{}]", old_name);
+            return Err(message);
+        }
+        if self.get_device(new_name).is_some() || self.service_schema.contains_device(new_name) {
+            let message = format!("Home already contains device[{}]", new_name);
+            return Err(message);
+        }
+
+        self.service_schema.rename_device(old_name, new_name)?;
+        self.service_devices.rename_device(old_name, new_name)
+    }
+
+    /// Method remove device and all devices connected to it
+    ///
+    /// Returns names of all removed devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room.name(), "S01", "T01").unwrap();
+    ///
+    /// let removed = service.remove_device_cascade("S01").unwrap();
+    ///
+    /// # assert_eq!(removed, vec!["S01", "T01"]);
+    /// # assert!(service.remove_device_cascade("S01").is_err()); // second remove is KO
+    /// ```
+    pub fn remove_device_cascade(&mut self, device_name: &str) -> Result<Vec<String>, String> {
+        if !self.service_schema.contains_device(device_name) {
+            let message = format!("No found device name [{}] at schema", device_name);
+            return Err(message);
+        }
+
+        let mut removed = vec![String::from(device_name)];
+        removed.extend(self.service_schema.descendants(device_name));
+
+        for name in removed.iter() {
+            self.remove_device(name)?;
+        }
+
+        Ok(removed)
+    }
+
     /// Method connect devices
-    /// TODO: rewrite method
     ///
+    /// If connection is new - return Ok(true)
+    /// If devices already connected - return Ok(false)
+    /// If connection is not valid (see can_connect) - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// # assert_eq!(service.connect_device(room.name(), "S01", "T01"), Ok(true)); // new connection
+    /// # assert_eq!(service.connect_device(room.name(), "S01", "T01"), Ok(false)); // existing connection
+    /// # assert!(service.connect_device(room.name(), "S01", "Unknown").is_err()); // unknown device
+    /// ```
     pub fn connect_device(
         &mut self,
         room_name: &str,
         device_connects_to: &str,
         device_connected: &str,
-    ) -> Result<(), String> {
+    ) -> Result<bool, String> {
+        self.can_connect(device_connects_to, device_connected)?;
+
         self.service_schema
             .connect_device(room_name, device_connects_to, device_connected)
-            .unwrap();
+    }
 
-        Ok(())
+    /// Method connect devices and return warnings about the connection
+    ///
+    /// Warning is returned, if device is newly connected to socket, which is off
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// let outcome = service.connect_device_with_warnings(room.name(), "S01", "T01").unwrap();
+    /// # assert!(outcome.created);
+    /// # assert_eq!(outcome.warnings.len(), 1);
+    /// # assert!(service.connect_device_with_warnings(room.name(), "S01", "T01").unwrap().warnings.is_empty());
+    /// ```
+    pub fn connect_device_with_warnings(
+        &mut self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<ConnectOutcome, String> {
+        let created = self.connect_device(room_name, device_connects_to, device_connected)?;
+
+        let mut warnings = vec![];
+        let parent_socket = self
+            .get_device(device_connects_to)
+            .and_then(|device| device.as_socket())
+            .filter(|_| created);
+        if let Some(socket) = parent_socket {
+            if !socket.is_enabled() {
+                warnings.push(format!(
+                    "Socket[{}] is off, device[{}] may be unpowered",
+                    device_connects_to, device_connected
+                ));
+            }
+        }
+
+        Ok(ConnectOutcome { created, warnings })
+    }
+
+    /// Method connect devices and return subtree of the parent device after connection
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// let node = service.connect_device_subtree(room.name(), "S01", "T01").unwrap();
+    /// # assert_eq!(node.name, "S01");
+    /// # assert_eq!(node.children[0].name, "T01");
+    /// ```
+    pub fn connect_device_subtree(
+        &mut self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<SchemaNode, String> {
+        self.connect_device(room_name, device_connects_to, device_connected)?;
+
+        self.subtree(device_connects_to)
+            .ok_or_else(|| format!("Home not contains device[{}]", device_connects_to))
+    }
+
+    /// Method connect devices and set label of the connection
+    ///
+    /// If connection already exists, its label is replaced.
+    /// If connection is new - return Ok(true)
+    /// If devices already connected - return Ok(false)
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// # assert_eq!(service.connect_device_labeled(room.name(), "S01", "T01", "powers"), Ok(true));
+    /// # assert_eq!(service.connection_label("S01", "T01"), Some("powers"));
+    /// ```
+    pub fn connect_device_labeled(
+        &mut self,
+        room_name: &str,
+        device_connects_to: &str,
+        device_connected: &str,
+        label: &str,
+    ) -> Result<bool, String> {
+        let created = self.connect_device(room_name, device_connects_to, device_connected)?;
+        self.service_schema
+            .set_connection_label(device_connects_to, device_connected, label)?;
+
+        Ok(created)
+    }
+
+    /// Method return label of connection between devices, if it was set
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room.name(), "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(service.connection_label("S01", "T01"), None);
+    /// ```
+    pub fn connection_label(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Option<&str> {
+        self.service_schema
+            .connection_label(device_connects_to, device_connected)
+    }
+
+    /// Method check connection of devices is valid without changing the schema
+    ///
+    /// Connection is valid, if both devices exist in the same room,
+    /// connected device is Connectable and connection doesn't create cycle
+    /// or exceed max depth. Existing connection is valid too
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// # assert!(service.can_connect("S01", "T01").is_ok());
+    /// # assert!(service.can_connect("T01", "S01").is_err()); // socket is not connectable
+    /// # assert!(service.can_connect("T01", "T01").is_err()); // connection to itself
+    /// ```
+    pub fn can_connect(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<(), String> {
+        self.check_devices_connectable(device_connects_to, device_connected)?;
+        self.service_schema
+            .require_same_room(device_connects_to, device_connected)?;
+
+        if self
+            .service_schema
+            .contains_connected_device(device_connects_to, device_connected)
+        {
+            return Ok(());
+        }
+        self.service_schema
+            .check_single_parent(device_connects_to, device_connected)?;
+        self.service_schema
+            .check_connection(device_connects_to, device_connected)
+    }
+
+    /// Method return error if any device not exists in devices store
+    /// or connected device is not Connectable
+    fn check_devices_connectable(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<(), String> {
+        self.service_devices.require_device(device_connects_to)?;
+        if !self.service_devices.require_device(device_connected)?.is_connectable() {
+            let message = format!("Device[{}] is not connectable", device_connected);
+            return Err(message);
+        }
+
+        Ok(())
+    }
+
+    /// Method connect device to device, which can be placed in another room
+    ///
+    /// If connection is new - return Ok(true)
+    /// If devices already connected - return Ok(false)
+    /// If any device not exists in its room or in devices store - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new("Kitchen")).unwrap();
+    /// service.add_room(&Room::new("Hall")).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device("Hall", Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// # assert_eq!(service.connect_cross_room("Kitchen", "S01", "Hall", "T01"), Ok(true));
+    /// # assert!(service.connect_cross_room("Kitchen", "S01", "Kitchen", "T01").is_err());
+    /// ```
+    pub fn connect_cross_room(
+        &mut self,
+        parent_room: &str,
+        device_connects_to: &str,
+        child_room: &str,
+        device_connected: &str,
+    ) -> Result<bool, String> {
+        self.check_devices_connectable(device_connects_to, device_connected)?;
+
+        self.service_schema.connect_cross_room(
+            parent_room,
+            device_connects_to,
+            child_room,
+            device_connected,
+        )
+    }
+
+    /// Method connect all pairs (device_connects_to, device_connected) as one operation
+    ///
+    /// Each connection is checked as in connect_device, so both devices must be
+    /// placed in the same room. All connections are checked together, before any of them
+    /// is applied. If any connection is not valid - return Error and schema stays unchanged
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new("Kitchen")).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device("Kitchen", Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.add_device("Kitchen", Box::new(Thermometer::from("T02", "Description"))).unwrap();
+    ///
+    /// # assert!(service.connect_devices_batch(&[("S01", "T01"), ("T01", "T02")]).is_ok());
+    /// # assert!(service.connect_devices_batch(&[("S01", "T02")]).is_err()); // T02 has parent
+    /// # assert_eq!(service.device_children("T01"), vec!["T02"]);
+    /// ```
+    pub fn connect_devices_batch(&mut self, edges: &[(&str, &str)]) -> Result<(), String> {
+        let mut staged_schema = self.service_schema.clone();
+        for (device_connects_to, device_connected) in edges {
+            self.check_devices_connectable(device_connects_to, device_connected)?;
+            staged_schema.connect_devices(device_connects_to, device_connected)?;
+        }
+
+        self.service_schema = staged_schema;
+        Ok(())
+    }
+
+    /// Method set max depth of connections chain
+    ///
+    /// Default value is DEFAULT_MAX_DEPTH
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.service_schema.max_depth = max_depth;
+    }
+
+    /// Method return max depth of connections chain
+    pub fn max_depth(&self) -> usize {
+        self.service_schema.max_depth
+    }
+
+    /// Method set max count of devices connected directly to one device
+    ///
+    /// None means unlimited, it is default value
+    pub fn set_max_children(&mut self, max_children: Option<usize>) {
+        self.service_schema.max_children = max_children;
+    }
+
+    /// Method return max count of devices connected directly to one device
+    pub fn max_children(&self) -> Option<usize> {
+        self.service_schema.max_children
+    }
+
+    /// Method disconnect device from parent device
+    ///
+    /// Disconnected device stays in its room, label of the connection is removed.
+    /// If devices are not connected - return Error
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room.name(), "S01", "T01").unwrap();
+    ///
+    /// # assert!(service.disconnect_device("S01", "T01").is_ok());
+    /// # assert!(service.disconnect_device("S01", "T01").is_err()); // not connected devices
+    /// ```
+    pub fn disconnect_device(
+        &mut self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<(), String> {
+        self.service_schema
+            .disconnect_device(device_connects_to, device_connected)
+    }
+
+    /// Method move connected device under another parent device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S02", "Description", 1000.0))).unwrap();
+    /// service.add_device(room.name(), Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room.name(), "S01", "T01").unwrap();
+    ///
+    /// let result: Result<(), String> = service.reparent_device("T01", "S02");
+    ///
+    /// # assert!(result.is_ok()); // normal reparent is OK
+    /// # assert_eq!(service.device_path("T01").unwrap(), vec!["My room", "S02", "T01"]);
+    /// ```
+    pub fn reparent_device(&mut self, device_name: &str, new_parent: &str) -> Result<(), String> {
+        self.service_schema.reparent_device(device_name, new_parent)
+    }
+
+    /// Method return device from store devices by specific name
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::Device;
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// let device_name = "My device";
+    /// let device = Socket::from(device_name, "Description", 1000.0);
+    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    ///
+    /// let device_opt: Option<&dyn Device> = service.get_device(device_name);
+    ///
+    /// # assert!(device_opt.is_some()); // normal get device return some is OK
+    /// # assert_eq!(device_opt.unwrap().name(), device_name); // normal get device is OK
+    /// # assert!(service.get_device("Unknown device").is_none()); // unknown get device is KO
+    /// ```
+    pub fn get_device(&self, device_name: &str) -> Option<&dyn Device> {
+        self.service_devices.get_device(device_name)
+    }
+
+    /// Method return identifier of the device, which is kept until device is removed
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// let id = service.device_id("S01").unwrap();
+    ///
+    /// # assert_eq!(service.get_device_by_id(id).unwrap().name(), "S01");
+    /// # assert!(service.device_id("Unknown device").is_none());
+    /// ```
+    pub fn device_id(&self, device_name: &str) -> Option<DeviceId> {
+        self.service_devices.id_of(device_name)
+    }
+
+    /// Method return device from store devices by its identifier
+    pub fn get_device_by_id(&self, id: DeviceId) -> Option<&dyn Device> {
+        self.service_devices.get_by_id(id)
+    }
+
+    /// Method remove device by its identifier from both services
+    ///
+    /// If device not found - return Error
+    pub fn remove_device_by_id(&mut self, id: DeviceId) -> Result<(), String> {
+        let device_name = match self.get_device_by_id(id) {
+            Some(device) => String::from(device.name()),
+            None => return Err(format!("Home not contains device with id [{:?}]", id)),
+        };
+
+        self.remove_device(device_name.as_str())
+    }
+
+    /// Method return device by its name or error, if device not found
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::Device;
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// let device = service.require_device("S01").unwrap();
+    ///
+    /// # assert_eq!(device.name(), "S01");
+    /// # assert!(service.require_device("Unknown device").is_err());
+    /// ```
+    pub fn require_device(&self, device_name: &str) -> Result<&dyn Device, String> {
+        self.service_devices.require_device(device_name)
+    }
+
+    /// Method return iter with list of devices names in the room
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// let device_name = "My device";
+    /// let device = Socket::from(device_name, "Description", 1000.0);
+    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    ///
+    /// let devices = service.get_devices(room_name);
+    /// # assert!(!devices.collect::<Vec<&String>>().is_empty()); // normal get devices is OK
+    /// # assert!(service.get_devices("Unknown room").collect::<Vec<&String>>().is_empty()); // unknown room get devices is KO
+    /// ```
+    pub fn get_devices(&self, room_name: &str) -> impl Iterator<Item = &String> {
+        self.service_schema.room_devices(room_name)
+    }
+
+    /// Method return list of devices in the room including connected devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// # assert_eq!(service.get_devices_recursive(room_name), vec!["S01"]);
+    /// # assert!(service.get_devices_recursive("Unknown room").is_empty());
+    /// ```
+    pub fn get_devices_recursive(&self, room_name: &str) -> Vec<String> {
+        self.service_schema.room_devices_recursive(room_name)
+    }
+
+    /// Method return statistics of the schema connections
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new(room_name)).unwrap();
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let stats = service.schema_stats();
+    /// # assert_eq!((stats.nodes, stats.edges, stats.max_depth), (2, 1, 1));
+    /// ```
+    pub fn schema_stats(&self) -> SchemaStats {
+        self.service_schema.collect_stats()
+    }
+
+    /// Method return each node of the schema (root, room or device) with its direct children
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new(room_name)).unwrap();
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let adjacency = service.adjacency_list();
+    /// # assert_eq!(adjacency[2], (String::from("S01"), vec![String::from("T01")]));
+    /// ```
+    pub fn adjacency_list(&self) -> Vec<(String, Vec<String>)> {
+        self.service_schema.adjacency_list()
+    }
+
+    /// Method return devices placed in the rooms, which are not connected to another device
+    ///
+    /// Devices are returned room by room, in order of adding
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new(room_name)).unwrap();
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(service.top_level_devices(), vec!["S01"]);
+    /// ```
+    pub fn top_level_devices(&self) -> Vec<String> {
+        self.service_schema.top_level_devices()
+    }
+
+    /// Method return iter with all devices from store devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// # assert_eq!(service.all_devices().count(), 1);
+    /// ```
+    pub fn all_devices(&self) -> Iter<'_, Box<dyn Device>> {
+        self.service_devices.all_devices()
+    }
+
+    /// Method return mutable iter with all devices from store devices
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// for device in service.all_devices_mut() {
+    ///     if let Some(socket) = device.as_socket_mut() {
+    ///         socket.power_on();
+    ///     }
+    /// }
+    ///
+    /// # assert!(service.get_device("S01").unwrap().as_socket().unwrap().is_enabled());
+    /// ```
+    pub fn all_devices_mut(&mut self) -> impl Iterator<Item = &mut dyn Device> {
+        self.service_devices.all_devices_mut()
+    }
+
+    /// Method return devices directly connected to the device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", ""))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// # assert_eq!(service.device_children("S01"), vec!["T01"]);
+    /// # assert!(service.device_children("T01").is_empty());
+    /// ```
+    pub fn device_children(&self, device_name: &str) -> Vec<String> {
+        self.service_schema.connections(device_name).cloned().collect()
+    }
+
+    /// Method return all devices connected to the device directly or through another devices
+    ///
+    /// Devices are returned depth-first, children in order of connection
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", ""))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T02", ""))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    /// service.connect_device(room_name, "T01", "T02").unwrap();
+    ///
+    /// # assert_eq!(service.descendants("S01"), vec!["T01", "T02"]);
+    /// ```
+    pub fn descendants(&self, device_name: &str) -> Vec<String> {
+        self.service_schema.descendants(device_name)
+    }
+
+    /// Method return tree of devices connected to the device
+    ///
+    /// Return None, if schema not contains device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// service.add_device(room_name, Box::new(Socket::from("S01", "", 1000.0))).unwrap();
+    /// service.add_device(room_name, Box::new(Thermometer::from("T01", ""))).unwrap();
+    /// service.connect_device(room_name, "S01", "T01").unwrap();
+    ///
+    /// let node = service.subtree("S01").unwrap();
+    /// # assert_eq!(node.children.len(), 1);
+    /// # assert!(service.subtree("Unknown").is_none());
+    /// ```
+    pub fn subtree(&self, device_name: &str) -> Option<SchemaNode> {
+        self.service_schema.subtree(device_name)
+    }
+
+    /// Method return path from the room through parent devices to the device
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// let device_name = "My device";
+    /// let device = Socket::from(device_name, "Description", 1000.0);
+    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    ///
+    /// let path = service.device_path(device_name);
+    /// # assert_eq!(path, Some(vec![String::from(room_name), String::from(device_name)]));
+    /// # assert!(service.device_path("Unknown device").is_none());
+    /// ```
+    pub fn device_path(&self, device_name: &str) -> Option<Vec<String>> {
+        self.service_schema.device_path(device_name)
+    }
+
+    /// Method cross-check devices store with schema store
+    ///
+    /// Returns empty list for consistent stores
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room = Room::new("My room");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device(room.name(), Box::new(Socket::from("S01", "Description", 1000.0))).unwrap();
+    ///
+    /// # assert!(service.validate().is_empty());
+    /// ```
+    pub fn validate(&self) -> Vec<Inconsistency> {
+        let mut result = vec![];
+
+        for device in self.service_devices.all_devices() {
+            if self.service_schema.room_of(device.name()).is_none() {
+                result.push(Inconsistency::OrphanedDevice(String::from(device.name())));
+            }
+        }
+
+        let mut schema_devices: Vec<&String> = self.service_schema.devices().collect();
+        schema_devices.sort();
+        for device_name in schema_devices {
+            if self.get_device(device_name).is_none() {
+                result.push(Inconsistency::DanglingLink(device_name.clone()));
+            }
+        }
+
+        for room in self.service_schema.rooms() {
+            for device_name in self.service_schema.room_devices(room) {
+                if self.get_device(device_name).is_none() {
+                    result.push(Inconsistency::MissingRoomDevice {
+                        room: room.clone(),
+                        device: device_name.clone(),
+                    });
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Test hook: add device only to devices store
+    #[cfg(test)]
+    pub(crate) fn add_device_to_store_only(&mut self, device: Box<dyn Device>) {
+        self.service_devices.store_devices.add_device(device);
+    }
+
+    /// Test hook: add device only to schema store
+    #[cfg(test)]
+    pub(crate) fn add_device_to_schema_only(&mut self, room_name: &str, device_name: &str) {
+        self.service_schema.store_schema.add_device(room_name, device_name).unwrap();
+    }
+
+    /// Method collect report and print it to stdout
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// let device_name = "My device";
+    /// let device = Socket::from(device_name, "Description", 1000.0);
+    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    ///
+    /// service.print_report();
+    /// ```
+    pub fn print_report(&self) {
+        println!("{}", self.collect_report());
+    }
+
+    /// Method collect report about all devices
+    ///
+    /// Report contains information about devices and, in distinct section,
+    /// values of devices which are can return them
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let room_name = "My room";
+    /// let room = Room::new(room_name);
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    ///
+    /// let device = Thermometer::from("T01", "Description");
+    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    ///
+    /// let report = service.collect_report();
+    /// # assert!(report.contains("Measurements:\nT01: sample info"));
+    /// ```
+    pub fn collect_report(&self) -> String {
+        collect_to_string(|buffer| self.write_report(buffer))
+    }
+
+    /// Method write report about all devices to the writer device by device
+    ///
+    /// Output is the same as `collect_report`
+    ///
+    /// Example:
+    /// ```
+    /// use crate::home::devices::thermometer::Thermometer;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::ServiceDeviceManagement;
+    ///
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&Room::new("My room")).unwrap();
+    /// service.add_device("My room", Box::new(Thermometer::from("T01", "Description"))).unwrap();
+    ///
+    /// let mut buffer: Vec<u8> = Vec::new();
+    /// service.write_report(&mut buffer).unwrap();
+    /// # assert_eq!(String::from_utf8(buffer).unwrap(), service.collect_report());
+    /// ```
+    pub fn write_report<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b"Generated report about all devices:\n")?;
+        self.service_devices.write_data_for_report(writer)?;
+        writer.write_all(b"Measurements:\n")?;
+        self.service_devices.write_measurements_report(writer)
     }
 
-    /// Method return device from store devices by specific name
+    /// Method return JSON of rooms and device links only, without device parameters
     ///
     /// Example:
     /// ```
-    /// use crate::home::devices::Device;
     /// use crate::home::devices::socket::Socket;
+    /// use crate::home::devices::thermometer::Thermometer;
     /// use crate::home::places::Room;
     /// use crate::home::services::ServiceDeviceManagement;
     ///
-    /// let room = Room::new("My room");
+    /// let room = Room::new("Kitchen");
     /// let mut service = ServiceDeviceManagement::new();
     /// service.add_room(&room).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "", 1000.0))).unwrap();
+    /// service.add_device("Kitchen", Box::new(Thermometer::from("T01", ""))).unwrap();
+    /// service.connect_device("Kitchen", "S01", "T01").unwrap();
     ///
-    /// let device_name = "My device";
-    /// let device = Socket::from(device_name, "Description", 1000.0);
-    /// service.add_device(room.name(), Box::new(device)).unwrap();
+    /// let json = service.schema_json();
+    /// # assert_eq!(
+    /// #     json,
+    /// #     r#"{"rooms":[{"name":"Kitchen","devices":["S01","T01"]}],"links":[{"from":"S01","to":"T01"}]}"#
+    /// # );
+    /// ```
+    pub fn schema_json(&self) -> String {
+        self.service_schema.collect_schema_json(JsonFormat::Compact)
+    }
+
+    /// Method return JSON of rooms and device links in requested format
     ///
-    /// let device_opt: Option<&dyn Device> = service.get_device(device_name);
+    /// Example:
+    /// ```
+    /// use crate::home::devices::socket::Socket;
+    /// use crate::home::places::Room;
+    /// use crate::home::services::{JsonFormat, ServiceDeviceManagement};
     ///
-    /// # assert!(device_opt.is_some()); // normal get device return some is OK
-    /// # assert_eq!(device_opt.unwrap().name(), device_name); // normal get device is OK
-    /// # assert!(service.get_device("Unknown device").is_none()); // unknown get device is KO
+    /// let room = Room::new("Kitchen");
+    /// let mut service = ServiceDeviceManagement::new();
+    /// service.add_room(&room).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "", 1000.0))).unwrap();
+    ///
+    /// let json = service.schema_json_with(JsonFormat::Pretty);
+    /// # assert_eq!(
+    /// #     json,
+    /// #     "{\n  \"rooms\": [\n    {\"name\": \"Kitchen\", \"devices\": [\"S01\"]}\n  ],\n  \"links\": []\n}"
+    /// # );
     /// ```
-    pub fn get_device(&self, device_name: &str) -> Option<&dyn Device> {
-        self.service_devices.get_device(device_name)
+    pub fn schema_json_with(&self, format: JsonFormat) -> String {
+        self.service_schema.collect_schema_json(format)
     }
 
-    /// Method return iter with list of devices names in the room
+    /// Method return schema of the home as text
     ///
     /// Example:
     /// ```
@@ -163,49 +1333,55 @@ impl ServiceDeviceManagement {
     /// use crate::home::places::Room;
     /// use crate::home::services::ServiceDeviceManagement;
     ///
-    /// let room_name = "My room";
-    /// let room = Room::new(room_name);
+    /// let room = Room::new("Kitchen");
     /// let mut service = ServiceDeviceManagement::new();
     /// service.add_room(&room).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "", 1000.0))).unwrap();
     ///
-    /// let device_name = "My device";
-    /// let device = Socket::from(device_name, "Description", 1000.0);
-    /// service.add_device(room.name(), Box::new(device)).unwrap();
-    ///
-    /// let devices = service.get_devices(room_name);
-    /// # assert!(!devices.collect::<Vec<&String>>().is_empty()); // normal get devices is OK
-    /// # assert!(service.get_devices("Unknown room").collect::<Vec<&String>>().is_empty()); // unknown room get devices is KO
+    /// let schema = service.collect_schema("My home");
+    /// # assert_eq!(schema, "[HOME] My home\n- [ROOM] Kitchen\n\t- [DEVICE] S01\n");
     /// ```
-    pub fn get_devices(&self, room_name: &str) -> Iter<String> {
-        self.service_schema.room_devices(room_name)
+    pub fn collect_schema(&self, home_name: &str) -> String {
+        self.service_schema.collect_schema(home_name)
     }
 
-    /// Method collect report and print it to stdout
+    /// Method return schema of the home as text in requested format
     ///
     /// Example:
     /// ```
     /// use crate::home::devices::socket::Socket;
     /// use crate::home::places::Room;
-    /// use crate::home::services::ServiceDeviceManagement;
+    /// use crate::home::services::{SchemaFormat, ServiceDeviceManagement};
     ///
-    /// let room_name = "My room";
-    /// let room = Room::new(room_name);
+    /// let room = Room::new("Kitchen");
     /// let mut service = ServiceDeviceManagement::new();
     /// service.add_room(&room).unwrap();
+    /// service.add_device("Kitchen", Box::new(Socket::from("S01", "", 1000.0))).unwrap();
     ///
-    /// let device_name = "My device";
-    /// let device = Socket::from(device_name, "Description", 1000.0);
-    /// service.add_device(room.name(), Box::new(device)).unwrap();
-    ///
-    /// service.print_report();
+    /// let format = SchemaFormat { indent: String::from("    "), bullet: String::from("+") };
+    /// let schema = service.collect_schema_with("My home", &format);
+    /// # assert_eq!(schema, "[HOME] My home\n+ [ROOM] Kitchen\n    + [DEVICE] S01\n");
     /// ```
-    pub fn print_report(&self) {
-        let report = self.service_devices.collect_data_for_report();
-        println!("Generated report about all devices:\n{}", report);
+    pub fn collect_schema_with(&self, home_name: &str, format: &SchemaFormat) -> String {
+        self.service_schema.collect_schema_with(home_name, format)
+    }
+
+    /// Method write schema of the home to the writer line by line
+    ///
+    /// Output is the same as `collect_schema_with`
+    pub fn write_schema<W: Write>(
+        &self,
+        writer: &mut W,
+        home_name: &str,
+        format: &SchemaFormat,
+    ) -> io::Result<()> {
+        self.service_schema.write_schema(writer, home_name, format)
     }
 
     /// Method collect schema information and print it to stdout
     ///
+    /// Home is named by root of the schema
+    ///
     /// Example:
     /// ```
     /// use crate::home::devices::socket::Socket;
@@ -224,8 +1400,8 @@ impl ServiceDeviceManagement {
     /// service.print_schema();
     /// ```
     pub fn print_schema(&self) {
-        let report = self.service_schema.collect_schema();
-        println!("{}", report);
+        let root_name = self.service_schema.store_schema.root_name();
+        print!("{}", self.collect_schema(root_name));
     }
 }
 
@@ -233,6 +1409,7 @@ impl ServiceDeviceManagement {
 ///
 /// Struct contains only store with all devices
 ///
+#[derive(Clone)]
 struct ServiceDevices {
     store_devices: StoreDevices,
 }
@@ -244,25 +1421,56 @@ impl ServiceDevices {
         ServiceDevices { store_devices }
     }
 
+    /// Method create new service with preallocated store
+    pub fn with_capacity(devices: usize) -> ServiceDevices {
+        let store_devices = StoreDevices::with_capacity(devices);
+        ServiceDevices { store_devices }
+    }
+
     /// Method add device to store
     ///
-    /// If device is unique - good
-    /// If not - return error
+    /// If device name is unique - return identifier of the device
+    /// If not - return error, which asks to rename device, if it has default name
     ///
-    pub fn add_device(&mut self, device: Box<dyn Device>) -> Result<(), String> {
-        if self.store_devices.contains(device.as_ref()) {
+    pub fn add_device(&mut self, device: Box<dyn Device>) -> Result<DeviceId, String> {
+        let default_names =
+            [socket::DEFAULT_NAME, thermometer::DEFAULT_NAME, power_strip::DEFAULT_NAME];
+        let is_default = default_names.contains(&device.name());
+        if is_default && self.contains_name(device.name()) {
+            let message =
+                format!("{} [{}], give the device its own name", DEFAULT_NAME_TAKEN, device.name());
+            Err(message)
+        } else if self.contains_name(device.name()) {
             let message = format!("Devices store already contains device [{}]", device.name());
             Err(message)
         } else {
-            self.store_devices.add_device(device);
-            Ok(())
+            Ok(self.store_devices.add_device(device))
         }
     }
 
+    /// Method check contains device with the name in store
+    pub fn contains_name(&self, device_name: &str) -> bool {
+        self.store_devices.contains_name(device_name)
+    }
+
     pub fn remove_device(&mut self, device_name: &str) -> Result<(), String> {
         self.store_devices.remove_device(device_name)
     }
 
+    pub fn rename_device(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        self.store_devices.rename_device(old_name, new_name)
+    }
+
+    /// Method return device by its identifier
+    pub fn get_by_id(&self, id: DeviceId) -> Option<&dyn Device> {
+        self.store_devices.get_by_id(id)
+    }
+
+    /// Method return identifier of the device with the name
+    pub fn id_of(&self, device_name: &str) -> Option<DeviceId> {
+        self.store_devices.id_of(device_name)
+    }
+
     /// Method return device by its name
     ///
     /// If device name founds - return Some
@@ -274,40 +1482,85 @@ impl ServiceDevices {
             .map(|found| found.as_ref())
     }
 
+    /// Method return device by its name
+    ///
+    /// If device name founds - return Ok
+    /// If not - return Error
+    pub fn require_device(&self, device_name: &str) -> Result<&dyn Device, String> {
+        match self.get_device(device_name) {
+            Some(device) => Ok(device),
+            None => Err(format!("Home not contains device[{}]", device_name)),
+        }
+    }
+
+    /// Method return Iter to the all devices
+    pub fn all_devices(&self) -> Iter<'_, Box<dyn Device>> {
+        self.store_devices.iter()
+    }
+
+    /// Method return mutable Iter to the all devices
+    pub fn all_devices_mut(&mut self) -> impl Iterator<Item = &mut dyn Device> {
+        self.store_devices.iter_mut()
+    }
+
     pub fn _remove_device(&self, _device: Box<dyn Device>) -> Result<(), String> {
         todo!()
     }
 
-    /// Method collect all information about each device
-    ///
-    /// Returns report about devices
-    pub fn collect_data_for_report(&self) -> String {
-        let mut info = String::from("");
+    /// Method write all information about each device
+    pub fn write_data_for_report<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for device in self.store_devices.iter() {
+            write!(writer, "{}\n\n", device.info())?;
+        }
+        Ok(())
+    }
+
+    /// Method write values of each Measurable device, line `name: value` for each device
+    pub fn write_measurements_report<W: Write>(&self, writer: &mut W) -> io::Result<()> {
         for device in self.store_devices.iter() {
-            info.push_str(device.info().as_str());
-            info.push_str("\n\n");
+            if let Some(measurable) = device.as_measurable() {
+                writeln!(writer, "{}: {}", device.name(), measurable.data())?;
+            }
         }
-        info
+        Ok(())
     }
 }
 
 /// Struct to manage store of the schema of the Home
+#[derive(Clone)]
 struct ServiceSchemaDevices {
     store_schema: StoreDeviceLinks,
+    max_depth: usize,
+    max_children: Option<usize>,
 }
 
 impl ServiceSchemaDevices {
     /// Method create new service
     pub fn new() -> Self {
         let store_schema = StoreDeviceLinks::new();
-        ServiceSchemaDevices { store_schema }
+        ServiceSchemaDevices {
+            store_schema,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_children: None,
+        }
+    }
+
+    /// Method create new service with preallocated store
+    pub fn with_capacity(rooms: usize, devices: usize) -> Self {
+        let store_schema = StoreDeviceLinks::with_capacity(rooms, devices);
+        ServiceSchemaDevices {
+            store_schema,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_children: None,
+        }
     }
 
     /// Method add room to the schema
     ///
     /// If room name is unique - return Ok
-    /// If room already exists - return Error
+    /// If room already exists or name is reserved - return Error
     pub fn add_room(&mut self, room_name: &str) -> Result<(), String> {
+        self.check_not_root(room_name)?;
         if self.store_schema.contains_room(room_name) {
             let message = format!("Room [{}] already contains in home", room_name);
             Err(message)
@@ -327,11 +1580,17 @@ impl ServiceSchemaDevices {
         }
     }
 
+    /// Method move room to new position, position is clamped to bounds
+    pub fn reorder_room(&mut self, room_name: &str, new_index: usize) -> Result<(), String> {
+        self.store_schema.reorder_room(room_name, new_index)
+    }
+
     /// Method add device to the room
     ///
     /// If room and device is unique - return Ok
-    /// If room OR device already exists - return Error
+    /// If room OR device already exists or device name is reserved - return Error
     pub fn add_device(&mut self, room_name: &str, device_name: &str) -> Result<(), String> {
+        self.check_not_root(device_name)?;
         if !self.store_schema.contains_room(room_name) {
             let message = format!("Home not contains room[{}]", room_name);
             return Err(message);
@@ -344,80 +1603,592 @@ impl ServiceSchemaDevices {
             return Err(message);
         }
 
-        self.store_schema.add_device(room_name, device_name);
-
-        Ok(())
+        self.store_schema.add_device(room_name, device_name)
     }
 
     pub fn remove_device(&mut self, device_name: &str) -> Result<(), String> {
         self.store_schema.remove_device(device_name)
     }
 
+    /// Method change name of the device in the schema
+    ///
+    /// If new name is reserved - return Error
+    pub fn rename_device(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        self.check_not_root(new_name)?;
+        self.store_schema.rename_device(old_name, new_name)
+    }
+
     /// Method connect one device to another
-    /// TODO: rewrite mechanism
+    ///
+    /// If connection is new - return Ok(true)
+    /// If devices already connected - return Ok(false)
     pub fn connect_device(
         &mut self,
         room_name: &str,
         device_connects_to: &str,
         device_connected: &str,
-    ) -> Result<(), String> {
-        if !self
+    ) -> Result<bool, String> {
+        self.connect_cross_room(room_name, device_connects_to, room_name, device_connected)
+    }
+
+    /// Method connect device to device, which can be placed in another room
+    ///
+    /// If connection is new - return Ok(true)
+    /// If devices already connected - return Ok(false)
+    pub fn connect_cross_room(
+        &mut self,
+        parent_room: &str,
+        device_connects_to: &str,
+        child_room: &str,
+        device_connected: &str,
+    ) -> Result<bool, String> {
+        self.require_in_room(child_room, device_connected)?;
+        self.require_in_room(parent_room, device_connects_to)?;
+        if self
             .store_schema
-            .contains_device_in_room(room_name, device_connected)
+            .contains_connected_device(device_connects_to, device_connected)
         {
+            return Ok(false);
+        }
+        self.check_single_parent(device_connects_to, device_connected)?;
+        self.check_connection(device_connects_to, device_connected)?;
+
+        let created = self
+            .store_schema
+            .connect_device(device_connects_to, device_connected);
+
+        Ok(created)
+    }
+
+    /// Method connect devices placed in the same room, room is found by devices
+    ///
+    /// If devices are placed in different rooms - return Error
+    pub fn connect_devices(
+        &mut self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<bool, String> {
+        let room_name = self
+            .require_same_room(device_connects_to, device_connected)?
+            .to_string();
+
+        self.connect_device(room_name.as_str(), device_connects_to, device_connected)
+    }
+
+    /// Method return room of both devices or error, if devices are placed in different rooms
+    pub fn require_same_room(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Result<&str, String> {
+        let parent_room = self.require_room_of(device_connects_to)?;
+        let device_room = self.require_room_of(device_connected)?;
+        if parent_room != device_room {
             let message = format!(
-                "Room[{}] not contains device[{}]",
-                room_name, device_connected
+                "Device[{}] placed in room[{}], but device[{}] placed in room[{}]",
+                device_connected, device_room, device_connects_to, parent_room
             );
             return Err(message);
         }
-        if !self
-            .store_schema
-            .contains_device_in_room(room_name, device_connects_to)
+
+        Ok(parent_room)
+    }
+
+    /// Method return room of device or error, if device is not placed in room
+    fn require_room_of(&self, device_name: &str) -> Result<&str, String> {
+        match self.store_schema.room_of(device_name) {
+            Some(room_name) => Ok(room_name),
+            None => {
+                let message = format!("Home not contains device[{}]", device_name);
+                Err(message)
+            }
+        }
+    }
+
+    /// Method disconnect device from its parent device
+    ///
+    /// If devices are not connected - return Error
+    pub fn disconnect_device(
+        &mut self,
+        parent_name: &str,
+        device_name: &str,
+    ) -> Result<(), String> {
+        self.store_schema.disconnect_device(parent_name, device_name)
+    }
+
+    /// Method move connected device under another parent device
+    ///
+    /// If both devices are in the same room and move doesn't create cycle - return Ok
+    /// If device is already connected to the new parent - return Ok without changes
+    /// If device is not connected or move is not allowed - return Error
+    pub fn reparent_device(&mut self, device_name: &str, new_parent: &str) -> Result<(), String> {
+        let room_name = self.require_room_of(device_name)?;
+        self.require_in_room(room_name, new_parent)?;
+        match self.store_schema.parent(device_name) {
+            None => {
+                let message = format!("Device[{}] is not connected to any device", device_name);
+                return Err(message);
+            }
+            Some(current_parent) if current_parent == new_parent => return Ok(()),
+            Some(_) => {}
+        }
+        self.check_connection(new_parent, device_name)?;
+
+        self.store_schema.reparent_device(device_name, new_parent)
+    }
+
+    /// Method return error if device already connected to another parent device
+    ///
+    /// Device can have only one parent, use reparent_device to move it
+    pub fn check_single_parent(&self, parent_name: &str, device_name: &str) -> Result<(), String> {
+        match self.store_schema.parent(device_name) {
+            Some(current_parent) if current_parent != parent_name => {
+                let message = format!(
+                    "Device[{}] already connected to device[{}], use reparent_device to move it",
+                    device_name, current_parent
+                );
+                Err(message)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Method return error if connection of device to the parent creates a cycle,
+    /// exceeds max depth or max children of the parent
+    pub fn check_connection(&self, parent_name: &str, device_name: &str) -> Result<(), String> {
+        if device_name == parent_name
+            || self
+                .store_schema
+                .descendants(device_name)
+                .iter()
+                .any(|name| name == parent_name)
         {
             let message = format!(
-                "Room[{}] not contains device[{}]",
-                room_name, device_connects_to
+                "Device[{}] can't be connected to device[{}], it creates a cycle",
+                device_name, parent_name
             );
             return Err(message);
         }
+        self.check_children(parent_name, device_name)?;
+
+        self.check_depth(parent_name, device_name)
+    }
+
+    /// Method return error if parent already has max children without the device
+    fn check_children(&self, parent_name: &str, device_name: &str) -> Result<(), String> {
+        let max_children = match self.max_children {
+            Some(max_children) => max_children,
+            None => return Ok(()),
+        };
+        let children = self
+            .store_schema
+            .connections(parent_name)
+            .filter(|name| name.as_str() != device_name)
+            .count();
+        if children >= max_children {
+            let message = format!(
+                "Device[{}] can't be connected to device[{}], it already has max {} children",
+                device_name, parent_name, max_children
+            );
+            Err(message)
+        } else {
+            Ok(())
+        }
+    }
 
+    /// Method set label of existing connection
+    pub fn set_connection_label(
+        &mut self,
+        device_connects_to: &str,
+        device_connected: &str,
+        label: &str,
+    ) -> Result<(), String> {
         self.store_schema
-            .connect_device(device_connects_to, device_connected);
+            .set_connection_label(device_connects_to, device_connected, label)
+    }
 
-        Ok(())
+    /// Method return label of connection
+    pub fn connection_label(
+        &self,
+        device_connects_to: &str,
+        device_connected: &str,
+    ) -> Option<&str> {
+        self.store_schema.connection_label(device_connects_to, device_connected)
+    }
+
+    /// Method check device connected to another device
+    pub fn contains_connected_device(&self, parent_name: &str, device_name: &str) -> bool {
+        self.store_schema.contains_connected_device(parent_name, device_name)
+    }
+
+    /// Method return error if room not contains device
+    fn require_in_room(&self, room_name: &str, device_name: &str) -> Result<(), String> {
+        if self.store_schema.contains_device_in_room(room_name, device_name) {
+            Ok(())
+        } else {
+            Err(format!("Room[{}] not contains device[{}]", room_name, device_name))
+        }
+    }
+
+    /// Method return error if connection of device to the parent exceeds max depth
+    fn check_depth(&self, parent_name: &str, device_name: &str) -> Result<(), String> {
+        let depth =
+            self.store_schema.depth(parent_name) + 1 + self.store_schema.height(device_name);
+        if depth > self.max_depth {
+            let message = format!(
+                "Connection of device[{}] to device[{}] exceeds max depth {}",
+                device_name, parent_name, self.max_depth
+            );
+            Err(message)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Method return error if name is reserved for the root of the schema
+    fn check_not_root(&self, name: &str) -> Result<(), String> {
+        if self.store_schema.is_root(name) {
+            let message = format!("Name [{}] is reserved for the root of the home schema", name);
+            Err(message)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Method return rooms of the schema
+    pub fn rooms(&self) -> impl Iterator<Item = &String> {
+        self.store_schema.rooms()
     }
 
     /// Method return devices in the room
-    pub fn room_devices(&self, room_name: &str) -> Iter<String> {
-        self.store_schema.entities(room_name)
+    pub fn room_devices(&self, room_name: &str) -> impl Iterator<Item = &String> {
+        self.store_schema.members(room_name)
+    }
+
+    /// Method check contains room in the schema
+    pub fn contains_room(&self, room_name: &str) -> bool {
+        self.store_schema.contains_room(room_name)
+    }
+
+    /// Method check contains device in the room
+    pub fn contains_device_in_room(&self, room_name: &str, device_name: &str) -> bool {
+        self.store_schema.contains_device_in_room(room_name, device_name)
+    }
+
+    /// Method check contains device in the schema
+    pub fn contains_device(&self, device_name: &str) -> bool {
+        self.store_schema.contains_device(device_name)
+    }
+
+    /// Method return devices directly connected to the device
+    pub fn connections(&self, device_name: &str) -> impl Iterator<Item = &String> {
+        self.store_schema.connections(device_name)
+    }
+
+    /// Method return all devices connected to the device directly or through another devices
+    pub fn descendants(&self, device_name: &str) -> Vec<String> {
+        self.store_schema.descendants(device_name)
+    }
+
+    /// Method return tree of devices connected to the device
+    ///
+    /// Connections deeper than max depth from the device are not included
+    pub fn subtree(&self, device_name: &str) -> Option<SchemaNode> {
+        if !self.store_schema.contains_device(device_name) {
+            return None;
+        }
+
+        Some(self.collect_node(device_name))
+    }
+
+    /// Method return devices in the room with connected devices
+    pub fn room_devices_recursive(&self, room_name: &str) -> Vec<String> {
+        self.store_schema.room_devices_recursive(room_name)
+    }
+
+    /// Method return names of all devices in the schema
+    pub fn devices(&self) -> impl Iterator<Item = &String> {
+        self.store_schema.devices()
+    }
+
+    /// Method collect statistics of the schema in one traversal
+    ///
+    /// Traversal starts from devices without parent, each device is visited only once
+    pub fn collect_stats(&self) -> SchemaStats {
+        let mut stats = SchemaStats {
+            nodes: 0,
+            edges: 0,
+            max_depth: 0,
+            max_fanout: 0,
+            rooms: self.store_schema.rooms().count(),
+        };
+        let mut visited: HashSet<&str> = HashSet::new();
+        let mut stack: Vec<(&str, usize)> = self
+            .collect_top_level_devices()
+            .into_iter()
+            .map(|device| (device.as_str(), 0))
+            .collect();
+
+        while let Some((device, depth)) = stack.pop() {
+            if !visited.insert(device) {
+                continue;
+            }
+            let fanout = self.store_schema.connections(device).count();
+            stats.nodes += 1;
+            stats.edges += fanout;
+            stats.max_depth = stats.max_depth.max(depth);
+            stats.max_fanout = stats.max_fanout.max(fanout);
+            stack.extend(
+                self.store_schema
+                    .connections(device)
+                    .map(|connected| (connected.as_str(), depth + 1)),
+            );
+        }
+
+        stats
+    }
+
+    /// Method return each node of the schema with its direct children
+    pub fn adjacency_list(&self) -> Vec<(String, Vec<String>)> {
+        self.store_schema.adjacency_list()
+    }
+
+    /// Method return room, which contains device
+    pub fn room_of(&self, device_name: &str) -> Option<&str> {
+        self.store_schema.room_of(device_name)
+    }
+
+    /// Method return devices of the rooms without parent device
+    pub fn top_level_devices(&self) -> Vec<String> {
+        self.collect_top_level_devices().into_iter().cloned().collect()
+    }
+
+    /// Method collect devices of the rooms without parent device, room by room
+    fn collect_top_level_devices(&self) -> Vec<&String> {
+        let connected: HashSet<&String> = self
+            .store_schema
+            .devices()
+            .flat_map(|device| self.store_schema.connections(device))
+            .collect();
+
+        self.store_schema
+            .rooms()
+            .flat_map(|room| self.store_schema.members(room))
+            .filter(|device| !connected.contains(device))
+            .collect()
+    }
+
+    /// Method return path from the room to the device
+    pub fn device_path(&self, device_name: &str) -> Option<Vec<String>> {
+        self.store_schema.device_path(device_name)
     }
 
     /// Method return schema of the Home
-    pub fn collect_schema(&self) -> String {
-        let mut result = String::from("Schema of home:\n");
+    ///
+    /// First line is the home, every room or device is placed one tab deeper than its parent
+    pub fn collect_schema(&self, home_name: &str) -> String {
+        self.collect_schema_with(home_name, &SchemaFormat::default())
+    }
+
+    /// Method return schema of the Home with indentation and bullet of the format
+    pub fn collect_schema_with(&self, home_name: &str, format: &SchemaFormat) -> String {
+        collect_to_string(|buffer| self.write_schema(buffer, home_name, format))
+    }
 
-        for room in self.store_schema.entities(HOME_NAME) {
-            let room_devices = self.collect_devices_inner(room.as_str(), 1);
-            result.push_str(format!("[ROOM] {}\n", room).as_str());
-            result.push_str(room_devices.as_str());
+    /// Method write schema of the Home to the writer
+    pub fn write_schema<W: Write>(
+        &self,
+        writer: &mut W,
+        home_name: &str,
+        format: &SchemaFormat,
+    ) -> io::Result<()> {
+        writeln!(writer, "[HOME] {}", home_name)?;
+
+        for room in self.store_schema.rooms() {
+            writeln!(writer, "{} [ROOM] {}", format.bullet, room)?;
+            self.write_devices_inner(writer, room.as_str(), 1, format)?;
         }
-        result.push_str("In development");
 
-        result
+        Ok(())
     }
 
-    /// Recursive method return information about entity (Room or Device) and his connections
-    fn collect_devices_inner(&self, from_name: &str, tabs: usize) -> String {
-        let mut result = String::from("");
-        let tabs_str = "\t".repeat(tabs);
+    /// Method collect rooms and device links of the schema to JSON
+    ///
+    /// Links are listed room by room, in order of devices in the schema.
+    /// Links to device of another room are marked with `"cross_room":true`,
+    /// labeled links contain `"label"`
+    pub fn collect_schema_json(&self, format: JsonFormat) -> String {
+        let (colon, comma) = match format {
+            JsonFormat::Pretty => (": ", ", "),
+            JsonFormat::Compact => (":", ","),
+        };
+        let mut rooms: Vec<String> = Vec::new();
+        let mut links: Vec<String> = Vec::new();
+
+        for room in self.store_schema.rooms() {
+            let devices: Vec<String> = self
+                .store_schema
+                .members(room)
+                .map(|device| format!("\"{}\"", escape_json(device)))
+                .collect();
+            rooms.push(format!(
+                "{{\"name\"{colon}\"{}\"{comma}\"devices\"{colon}[{}]}}",
+                escape_json(room),
+                devices.join(comma)
+            ));
 
-        for device in self.store_schema.entities(from_name) {
-            let devices_inner = self.collect_devices_inner(device.as_str(), tabs + 1);
-            result.push_str(format!("{}- {}\n", tabs_str, device).as_str());
-            result.push_str(devices_inner.as_str());
+            for device in self.store_schema.members(room) {
+                for (kind, connected) in self.store_schema.links(device) {
+                    let mut link = format!(
+                        "{{\"from\"{colon}\"{}\"{comma}\"to\"{colon}\"{}\"",
+                        escape_json(device),
+                        escape_json(connected)
+                    );
+                    if let LinkKind::CrossRoom = kind {
+                        link.push_str(format!("{comma}\"cross_room\"{colon}true").as_str());
+                    }
+                    if let Some(label) = self.store_schema.connection_label(device, connected) {
+                        let label = escape_json(label);
+                        link.push_str(format!("{comma}\"label\"{colon}\"{}\"", label).as_str());
+                    }
+                    link.push('}');
+                    links.push(link);
+                }
+            }
         }
 
-        result
+        match format {
+            JsonFormat::Pretty => format!(
+                "{{\n  \"rooms\": {},\n  \"links\": {}\n}}",
+                pretty_json_array(&rooms),
+                pretty_json_array(&links)
+            ),
+            JsonFormat::Compact => {
+                format!("{{\"rooms\":[{}],\"links\":[{}]}}", rooms.join(","), links.join(","))
+            }
+        }
+    }
+
+    /// Method build node of the device with all connected devices
+    ///
+    /// Tree is built with explicit stack of nodes, which children are not collected yet
+    fn collect_node(&self, device_name: &str) -> SchemaNode {
+        let new_frame = |name: &str| -> (SchemaNode, IntoIter<&String>) {
+            let node = SchemaNode {
+                name: name.to_string(),
+                children: vec![],
+            };
+            let connections: Vec<&String> = self.store_schema.connections(name).collect();
+            (node, connections.into_iter())
+        };
+        let mut stack = vec![new_frame(device_name)];
+
+        loop {
+            let depth = stack.len() - 1;
+            let next = stack.last_mut().and_then(|(_, connections)| connections.next());
+            match next {
+                Some(connected) if depth < self.max_depth => stack.push(new_frame(connected)),
+                Some(_) => {}
+                None => {
+                    let (node, _) = stack.pop().expect("Stack contains node of the device");
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.children.push(node),
+                        None => return node,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Method return information about entity (Room or Device) and his connections
+    ///
+    /// Devices placed in the room are marked as [DEVICE],
+    /// devices connected to another device are marked as [CONNECTED],
+    /// devices connected to device of another room are marked as [CROSS-ROOM].
+    /// Label of connection is shown in brackets after device name
+    /// Devices connected to device of the same room are shown only under their parent
+    /// Connections deeper than max depth are not shown
+    fn write_devices_inner<W: Write>(
+        &self,
+        writer: &mut W,
+        from_name: &str,
+        tabs: usize,
+        format: &SchemaFormat,
+    ) -> io::Result<()> {
+        let mut stack: Vec<(LinkKind, &str, &str, usize)> = self
+            .store_schema
+            .links(from_name)
+            .into_iter()
+            .rev()
+            .filter(|(kind, device)| match kind {
+                LinkKind::Membership => self
+                    .store_schema
+                    .parent(device)
+                    .and_then(|parent| self.store_schema.room_of(parent))
+                    .is_none_or(|parent_room| parent_room != from_name),
+                _ => true,
+            })
+            .map(|(kind, device)| (kind, from_name, device, tabs))
+            .collect();
+
+        while let Some((kind, parent, device, tabs)) = stack.pop() {
+            let label = match kind {
+                LinkKind::Membership => "[DEVICE]",
+                LinkKind::Connection => "[CONNECTED]",
+                LinkKind::CrossRoom => "[CROSS-ROOM]",
+            };
+            let indent = format.indent.repeat(tabs);
+            write!(writer, "{}{} {} {}", indent, format.bullet, label, device)?;
+            match self.store_schema.connection_label(parent, device) {
+                Some(connection_label) => writeln!(writer, " ({})", connection_label)?,
+                None => writeln!(writer)?,
+            }
+
+            if tabs <= self.max_depth {
+                let links = self.store_schema.links(device).into_iter().rev();
+                stack.extend(links.map(|(kind, connected)| (kind, device, connected, tabs + 1)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Function collect text written by the function into String
+fn collect_to_string<F>(write: F) -> String
+where
+    F: FnOnce(&mut Vec<u8>) -> io::Result<()>,
+{
+    let mut buffer: Vec<u8> = Vec::new();
+    write(&mut buffer).expect("Writing to memory buffer can't fail");
+    String::from_utf8(buffer).expect("Text is written from strings, so it is valid UTF-8")
+}
+
+/// Function escape string to use it as JSON string value
+fn escape_json(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for symbol in value.chars() {
+        match symbol {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            symbol if symbol.is_control() => {
+                result.push_str(format!("\\u{:04x}", symbol as u32).as_str())
+            }
+            symbol => result.push(symbol),
+        }
+    }
+
+    result
+}
+
+/// Function place every item of JSON array on its own line
+fn pretty_json_array(items: &[String]) -> String {
+    if items.is_empty() {
+        return String::from("[]");
     }
+
+    format!("[\n    {}\n  ]", items.join(",\n    "))
 }
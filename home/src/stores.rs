@@ -1,14 +1,27 @@
-use std::{collections::HashMap, slice::Iter};
+use std::{
+    collections::{HashMap, HashSet},
+    slice::Iter,
+};
 
-use crate::devices::Device;
+use crate::devices::{Device, RenameToken};
 
 pub const HOME_NAME: &str = "HOME";
 
+/// Identifier of the device in devices store
+///
+/// Identifier is given by store on adding and is not changed until device is removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceId(u64);
+
 /// Structure to store Device
 ///
-/// Contains only property devices, which store <{Device}>
+/// Contains only property devices, which store <{Device}>.
+/// Identifiers are kept in order of adding, so they are sorted
+#[derive(Clone)]
 pub struct StoreDevices {
     devices: Vec<Box<dyn Device>>,
+    ids: Vec<DeviceId>,
+    next_id: u64,
 }
 
 impl Default for StoreDevices {
@@ -20,7 +33,20 @@ impl Default for StoreDevices {
 impl StoreDevices {
     /// Method create new Store
     pub fn new() -> StoreDevices {
-        StoreDevices { devices: vec![] }
+        StoreDevices {
+            devices: vec![],
+            ids: vec![],
+            next_id: 0,
+        }
+    }
+
+    /// Method create new Store with preallocated place for devices
+    pub fn with_capacity(devices: usize) -> StoreDevices {
+        StoreDevices {
+            devices: Vec::with_capacity(devices),
+            ids: Vec::with_capacity(devices),
+            next_id: 0,
+        }
     }
 
     /// Method check contains device in the store
@@ -30,15 +56,25 @@ impl StoreDevices {
             .any(|d| d.name() == device.name() && d.info() == device.info())
     }
 
-    /// Method add device to the store
-    pub fn add_device(&mut self, device: Box<dyn Device>) {
-        self.devices.push(device)
+    /// Method check contains device with the name in the store
+    pub fn contains_name(&self, device_name: &str) -> bool {
+        self.devices.iter().any(|device| device.name() == device_name)
+    }
+
+    /// Method add device to the store and return its identifier
+    pub fn add_device(&mut self, device: Box<dyn Device>) -> DeviceId {
+        let id = DeviceId(self.next_id);
+        self.next_id += 1;
+        self.devices.push(device);
+        self.ids.push(id);
+        id
     }
 
     pub fn remove_device(&mut self, device_name: &str) -> Result<(), String> {
         match self.devices.iter().position(|device| device.name() == device_name) {
             Some(index) => {
                 self.devices.remove(index);
+                self.ids.remove(index);
                 Ok(())
             },
             None => {
@@ -48,15 +84,84 @@ impl StoreDevices {
         }
     }
 
+    /// Method remove device by its identifier and return it
+    pub fn remove_by_id(&mut self, id: DeviceId) -> Result<Box<dyn Device>, String> {
+        match self.ids.binary_search(&id) {
+            Ok(index) => {
+                self.ids.remove(index);
+                Ok(self.devices.remove(index))
+            }
+            Err(_) => {
+                let message = format!("Not found device with id [{:?}] in Devices Store", id);
+                Err(message)
+            }
+        }
+    }
+
+    /// Method return device by its identifier
+    pub fn get_by_id(&self, id: DeviceId) -> Option<&dyn Device> {
+        let index = self.ids.binary_search(&id).ok()?;
+        Some(self.devices[index].as_ref())
+    }
+
+    /// Method return identifier of the device with the name
+    pub fn id_of(&self, device_name: &str) -> Option<DeviceId> {
+        let index = self.devices.iter().position(|device| device.name() == device_name)?;
+        Some(self.ids[index])
+    }
+
+    /// Method change name of the device in the store
+    pub fn rename_device(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        match self.devices.iter_mut().find(|device| device.name() == old_name) {
+            Some(device) => {
+                device.set_name(new_name, RenameToken::new());
+                Ok(())
+            }
+            None => {
+                let message = format!("Not found device with name [{}] in Devices Store", old_name);
+                Err(message)
+            }
+        }
+    }
+
     /// Method return Iter to the all devices
-    pub fn iter(&self) -> Iter<Box<dyn Device>> {
+    pub fn iter(&self) -> Iter<'_, Box<dyn Device>> {
         self.devices.iter()
     }
+
+    /// Method return mutable Iter to the all devices
+    ///
+    /// Devices can't be replaced or renamed through it, use `rename_device` to change name
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut dyn Device> {
+        self.devices.iter_mut().map(|device| -> &mut dyn Device { device.as_mut() })
+    }
+}
+
+/// Kind of the link between entities of the schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkKind {
+    /// Room placed in the Home or device placed in the Room
+    Membership,
+    /// Device connected to another device
+    Connection,
+    /// Device connected to another device, which is placed in another room
+    CrossRoom,
 }
 
 /// Struct of store schema of the Home
+///
+/// Contains two kinds of links:
+/// - members: Home -> Rooms and Room -> Devices
+/// - connections: Device -> connected Devices
+///
+/// Connections can have optional labels, stored by pair (parent, connected device).
+/// Root of the schema is stored under reserved name (HOME_NAME by default)
+#[derive(Clone)]
 pub struct StoreDeviceLinks {
-    links: HashMap<String, Vec<String>>,
+    root_name: String,
+    members: HashMap<String, Vec<String>>,
+    connections: HashMap<String, Vec<String>>,
+    labels: HashMap<(String, String), String>,
 }
 
 impl Default for StoreDeviceLinks {
@@ -68,15 +173,49 @@ impl Default for StoreDeviceLinks {
 impl StoreDeviceLinks {
     /// Method create new store
     pub fn new() -> Self {
-        let mut links = HashMap::new();
-        links.insert(String::from(HOME_NAME), Vec::<String>::new());
+        Self::with_capacity(0, 0)
+    }
+
+    /// Method create new store with preallocated place for rooms and devices
+    pub fn with_capacity(rooms: usize, devices: usize) -> Self {
+        Self::create(HOME_NAME, rooms, devices)
+    }
+
+    /// Method create new store with specific name of the root
+    pub fn with_root(root_name: &str) -> Self {
+        Self::create(root_name, 0, 0)
+    }
 
-        StoreDeviceLinks { links }
+    fn create(root_name: &str, rooms: usize, devices: usize) -> Self {
+        let mut members = HashMap::with_capacity(rooms + 1);
+        members.insert(String::from(root_name), Vec::<String>::with_capacity(rooms));
+
+        StoreDeviceLinks {
+            root_name: String::from(root_name),
+            members,
+            connections: HashMap::with_capacity(devices),
+            labels: HashMap::new(),
+        }
+    }
+
+    /// Method return reserved name of the root
+    pub fn root_name(&self) -> &str {
+        self.root_name.as_str()
+    }
+
+    /// Method check name is reserved name of the root
+    pub fn is_root(&self, name: &str) -> bool {
+        self.root_name == name
+    }
+
+    /// Method return rooms of the schema
+    pub fn rooms(&self) -> impl Iterator<Item = &String> {
+        self.members(self.root_name.as_str())
     }
 
     /// Method check contains room in keys
     pub fn contains_room(&self, room_name: &str) -> bool {
-        self.links.contains_key(&String::from(room_name))
+        self.members.contains_key(&String::from(room_name))
     }
 
     /// Method add room to store
@@ -84,11 +223,11 @@ impl StoreDeviceLinks {
     /// If room is unique - its Ok
     /// If not - panic
     pub fn add_room(&mut self, room_name: &str) {
-        match self.links.get_mut(&String::from(HOME_NAME)) {
+        match self.members.get_mut(&self.root_name) {
             Some(home) => home.push(String::from(room_name)),
             None => panic!("Create StoreDeviceLinks without root HOME"),
         }
-        self.links.insert(String::from(room_name), vec![]);
+        self.members.insert(String::from(room_name), vec![]);
     }
 
     /// Method remove room from store
@@ -96,7 +235,7 @@ impl StoreDeviceLinks {
     /// If room exists - its OK
     /// If not - panic
     pub fn remove_room(&mut self, room_name: &str) -> Result<(), String> {
-        match self.links.get_mut(&String::from(HOME_NAME)) {
+        match self.members.get_mut(&self.root_name) {
             Some(home) => {
                 match home.iter().position(|room| room.as_str() == room_name) {
                     Some(index) => {
@@ -111,44 +250,72 @@ impl StoreDeviceLinks {
             None => panic!("Create StoreDeviceLinks without root HOME"),
         }
 
-        self.links.remove(room_name);
+        self.members.remove(room_name);
 
         Ok(())
     }
 
+    /// Method move room to new position in rooms of the root
+    ///
+    /// New position is clamped to bounds of rooms list
+    pub fn reorder_room(&mut self, room_name: &str, new_index: usize) -> Result<(), String> {
+        let rooms = match self.members.get_mut(&self.root_name) {
+            Some(rooms) => rooms,
+            None => panic!("Create StoreDeviceLinks without root HOME"),
+        };
+        match rooms.iter().position(|room| room.as_str() == room_name) {
+            Some(index) => {
+                let room = rooms.remove(index);
+                rooms.insert(new_index.min(rooms.len()), room);
+                Ok(())
+            }
+            None => {
+                let message = format!("Room with name [{}] not found in Schemas store", room_name);
+                Err(message)
+            }
+        }
+    }
+
     pub fn contains_device(&self, device_name: &str) -> bool {
-        self.links.contains_key(&String::from(device_name))
+        self.connections.contains_key(&String::from(device_name))
     }
 
     /// Method check contains device in the room
     pub fn contains_device_in_room(&self, room_name: &str, device_name: &str) -> bool {
-        let contains_in_room = match self.links.get(&String::from(room_name)) {
+        let contains_in_room = match self.members.get(&String::from(room_name)) {
             None => false,
             Some(room_devices) => room_devices.contains(&String::from(device_name)),
         };
-        let contains_in_list = match self.links.get(&String::from(device_name)) {
-            None => false,
-            Some(_) => true,
-        };
+        let contains_in_list = self.connections.contains_key(&String::from(device_name));
 
         contains_in_list && contains_in_room
     }
 
     /// Method add device to the room
     /// If room contains - OK
-    /// If not - panic
-    pub fn add_device(&mut self, room_name: &str, device_name: &str) {
-        match self.links.get_mut(&String::from(room_name)) {
+    /// If device already exists in any room - return Error, store is not changed
+    /// If room not contains - panic
+    pub fn add_device(&mut self, room_name: &str, device_name: &str) -> Result<(), String> {
+        if self.connections.contains_key(device_name) {
+            let message = format!("Device name [{}] already exists at schema", device_name);
+            return Err(message);
+        }
+        match self.members.get_mut(&String::from(room_name)) {
             None => {
                 panic!("Something wrong, room must be exists")
             }
             Some(room_devices) => room_devices.push(String::from(device_name)),
         }
-        self.links.insert(String::from(device_name), vec![]);
+        self.connections.insert(String::from(device_name), vec![]);
+
+        Ok(())
     }
 
+    /// Method remove device from store
+    ///
+    /// Device is removed from its room and from all connections
     pub fn remove_device(&mut self, device_name: &str) -> Result<(), String> {
-        match self.links.remove(device_name) {
+        match self.connections.remove(device_name) {
             Some(_) => {},
             None => {
                 let message = format!("No found device name [{}] at schema", device_name);
@@ -156,15 +323,45 @@ impl StoreDeviceLinks {
             }
         }
 
-        for (_, entities) in self.links.iter_mut() {
-            match entities.iter().position(|name| name == device_name) {
-                None => {},
-                Some(index) => {
-                    entities.remove(index);
-                    return Ok(())
-                }
+        for entities in self.members.values_mut().chain(self.connections.values_mut()) {
+            entities.retain(|name| name != device_name);
+        }
+        self.labels
+            .retain(|(parent, connected), _| parent != device_name && connected != device_name);
+
+        Ok(())
+    }
+
+    /// Method change name of the device in room members, connections and own links
+    pub fn rename_device(&mut self, old_name: &str, new_name: &str) -> Result<(), String> {
+        let connected_devices = match self.connections.remove(old_name) {
+            Some(connected_devices) => connected_devices,
+            None => {
+                let message = format!("No found device name [{}] at schema", old_name);
+                return Err(message);
+            }
+        };
+        self.connections.insert(String::from(new_name), connected_devices);
+
+        for entities in self.members.values_mut().chain(self.connections.values_mut()) {
+            for name in entities.iter_mut().filter(|name| name.as_str() == old_name) {
+                *name = String::from(new_name);
             }
         }
+        self.labels = self
+            .labels
+            .drain()
+            .map(|((parent, connected), label)| {
+                let rename = |name: String| {
+                    if name == old_name {
+                        String::from(new_name)
+                    } else {
+                        name
+                    }
+                };
+                ((rename(parent), rename(connected)), label)
+            })
+            .collect();
 
         Ok(())
     }
@@ -172,37 +369,290 @@ impl StoreDeviceLinks {
     /// Method check contains connected devices
     /// TODO: rewrite mechanism
     pub fn contains_connected_device(&self, device_to: &str, device_from: &str) -> bool {
-        match self.links.get(&String::from(device_to)) {
+        match self.connections.get(&String::from(device_to)) {
             None => false,
             Some(connected_devices) => connected_devices.contains(&String::from(device_from)),
         }
     }
 
     /// Method connect devices
-    /// TODO: rewrite mechanism
-    pub fn connect_device(&mut self, device_name_to: &str, device_name_from: &str) {
-        match self.links.get_mut(&String::from(device_name_to)) {
+    ///
+    /// If connection is new - return true
+    /// If devices already connected - return false
+    ///
+    /// Connected devices are kept in order of connection
+    pub fn connect_device(&mut self, device_name_to: &str, device_name_from: &str) -> bool {
+        match self.connections.get_mut(&String::from(device_name_to)) {
             None => {
                 panic!("Device[{}] doesn't exists in schema store", device_name_to);
             }
             Some(connected_devices) => {
                 if connected_devices.contains(&String::from(device_name_from)) {
-                    panic!(
-                        "In device[{}] already connected another device[{}]",
-                        device_name_to, device_name_from
-                    );
+                    false
                 } else {
                     connected_devices.push(String::from(device_name_from));
+                    true
                 }
             }
         }
     }
 
-    /// Method return connections of entity (Room or Device)
-    pub fn entities(&self, name: &str) -> Iter<String> {
-        match self.links.get(name) {
-            None => [].iter(),
-            Some(entities) => entities.iter(),
+    /// Method disconnect devices, label of the connection is removed too
+    ///
+    /// Order of other connected devices is kept.
+    /// If devices are not connected - return Error
+    pub fn disconnect_device(
+        &mut self,
+        device_name_to: &str,
+        device_name_from: &str,
+    ) -> Result<(), String> {
+        let connected_devices = self.connections.get_mut(device_name_to);
+        let index = connected_devices
+            .as_ref()
+            .and_then(|devices| devices.iter().position(|name| name == device_name_from));
+        match (connected_devices, index) {
+            (Some(connected_devices), Some(index)) => {
+                connected_devices.remove(index);
+            }
+            _ => {
+                let message = format!(
+                    "Device[{}] is not connected to device[{}]",
+                    device_name_from, device_name_to
+                );
+                return Err(message);
+            }
         }
+
+        let key = (String::from(device_name_to), String::from(device_name_from));
+        self.labels.remove(&key);
+        Ok(())
+    }
+
+    /// Method set label of existing connection between devices
+    ///
+    /// If devices are not connected - return Error
+    pub fn set_connection_label(
+        &mut self,
+        device_name_to: &str,
+        device_name_from: &str,
+        label: &str,
+    ) -> Result<(), String> {
+        if !self.contains_connected_device(device_name_to, device_name_from) {
+            let message = format!(
+                "Device[{}] is not connected to device[{}]",
+                device_name_from, device_name_to
+            );
+            return Err(message);
+        }
+
+        let key = (String::from(device_name_to), String::from(device_name_from));
+        self.labels.insert(key, String::from(label));
+        Ok(())
+    }
+
+    /// Method return label of connection between devices, if it was set
+    pub fn connection_label(&self, device_name_to: &str, device_name_from: &str) -> Option<&str> {
+        let key = (String::from(device_name_to), String::from(device_name_from));
+        self.labels.get(&key).map(|label| label.as_str())
+    }
+
+    /// Method return members of entity (rooms of the Home or devices of the Room)
+    ///
+    /// For unknown entity - return empty iterator
+    pub fn members(&self, name: &str) -> impl Iterator<Item = &String> {
+        self.members.get(name).into_iter().flatten()
+    }
+
+    /// Method return devices connected to the device
+    ///
+    /// For unknown device - return empty iterator
+    pub fn connections(&self, device_name: &str) -> impl Iterator<Item = &String> {
+        self.connections.get(device_name).into_iter().flatten()
+    }
+
+    /// Method return names of all devices in the store
+    pub fn devices(&self) -> impl Iterator<Item = &String> {
+        self.connections.keys()
+    }
+
+    /// Method return parent device, which device connected to
+    pub fn parent(&self, device_name: &str) -> Option<&str> {
+        self.connections
+            .iter()
+            .find(|(_, connected)| connected.iter().any(|name| name == device_name))
+            .map(|(parent, _)| parent.as_str())
+    }
+
+    /// Method return all devices connected to the device directly or through another devices
+    ///
+    /// Each device is returned only once, even if connections contain cycle
+    pub fn descendants(&self, device_name: &str) -> Vec<String> {
+        let mut result = vec![];
+        let mut visited = HashSet::from([device_name]);
+        let mut stack: Vec<&str> = self.connections(device_name).map(|name| name.as_str()).collect();
+        stack.reverse();
+
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            result.push(String::from(current));
+
+            let children: Vec<&str> = self.connections(current).map(|name| name.as_str()).collect();
+            stack.extend(children.into_iter().rev());
+        }
+
+        result
+    }
+
+    /// Method return each node (root, room or device) with its direct children
+    ///
+    /// Root goes first, then rooms and then devices room by room, all in order of adding
+    pub fn adjacency_list(&self) -> Vec<(String, Vec<String>)> {
+        let places = std::iter::once(&self.root_name)
+            .chain(self.rooms())
+            .map(|place| (place.clone(), self.members(place).cloned().collect()));
+        let devices = self
+            .rooms()
+            .flat_map(|room| self.members(room))
+            .map(|device| (device.clone(), self.connections(device).cloned().collect()));
+
+        places.chain(devices).collect()
+    }
+
+    /// Method return devices of the room together with all their connected devices
+    ///
+    /// Each device is returned only once, even if connections contain cycle
+    pub fn room_devices_recursive(&self, room_name: &str) -> Vec<String> {
+        let mut result: Vec<String> = vec![];
+
+        for device in self.members(room_name) {
+            let devices = std::iter::once(device.clone()).chain(self.descendants(device));
+            for device in devices {
+                if !result.contains(&device) {
+                    result.push(device);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Method move device from connections of its parent to connections of new parent
+    ///
+    /// If device has parent and new parent exists - OK
+    /// If not - return Error
+    pub fn reparent_device(&mut self, device_name: &str, new_parent: &str) -> Result<(), String> {
+        let parent = match self.parent(device_name) {
+            Some(parent) => String::from(parent),
+            None => {
+                let message = format!("Device[{}] is not connected to any device", device_name);
+                return Err(message);
+            }
+        };
+        if !self.connections.contains_key(new_parent) {
+            let message = format!("Device[{}] doesn't exists in schema store", new_parent);
+            return Err(message);
+        }
+
+        if let Some(connected_devices) = self.connections.get_mut(&parent) {
+            connected_devices.retain(|name| name != device_name);
+        }
+        self.labels.remove(&(parent, String::from(device_name)));
+        if let Some(connected_devices) = self.connections.get_mut(new_parent) {
+            connected_devices.push(String::from(device_name));
+        }
+
+        Ok(())
+    }
+
+    /// Method return room, which contains device
+    pub fn room_of(&self, device_name: &str) -> Option<&str> {
+        self.rooms()
+            .find(|room| self.members(room).any(|name| name == device_name))
+            .map(|room| room.as_str())
+    }
+
+    /// Method return parents of the device from the nearest to the farthest
+    ///
+    /// Each parent is returned only once, even if connections contain cycle
+    pub fn ancestors(&self, device_name: &str) -> Vec<&str> {
+        let mut result = vec![];
+        let mut visited = HashSet::from([device_name]);
+        let mut current = device_name;
+
+        while let Some(parent) = self.parent(current) {
+            if !visited.insert(parent) {
+                break;
+            }
+            result.push(parent);
+            current = parent;
+        }
+
+        result
+    }
+
+    /// Method return count of parents of the device
+    pub fn depth(&self, device_name: &str) -> usize {
+        self.ancestors(device_name).len()
+    }
+
+    /// Method return length of the longest chain of devices connected below the device
+    pub fn height(&self, device_name: &str) -> usize {
+        let mut height = 0;
+        let mut visited = HashSet::from([device_name]);
+        let mut stack = vec![(device_name, 0)];
+
+        while let Some((current, level)) = stack.pop() {
+            height = height.max(level);
+            for connected in self.connections(current) {
+                if visited.insert(connected.as_str()) {
+                    stack.push((connected.as_str(), level + 1));
+                }
+            }
+        }
+
+        height
+    }
+
+    /// Method return path from the room through parent devices to the device
+    ///
+    /// If device found - return Some with path
+    /// If not - return None
+    pub fn device_path(&self, device_name: &str) -> Option<Vec<String>> {
+        if !self.contains_device(device_name) {
+            return None;
+        }
+
+        let ancestors = self.ancestors(device_name);
+        let top_device = ancestors.last().copied().unwrap_or(device_name);
+
+        let mut path = vec![];
+        if let Some(room) = self.room_of(top_device) {
+            path.push(String::from(room));
+        }
+        path.extend(ancestors.iter().rev().map(|&parent| String::from(parent)));
+        path.push(String::from(device_name));
+
+        Some(path)
+    }
+
+    /// Method return all links of entity (Room or Device) with their kinds
+    ///
+    /// Connection to device of another room is returned as CrossRoom
+    pub fn links(&self, name: &str) -> Vec<(LinkKind, &str)> {
+        let room = self.room_of(name);
+        let members = self
+            .members(name)
+            .map(|member| (LinkKind::Membership, member.as_str()));
+        let connections = self.connections(name).map(|connected| {
+            if self.room_of(connected) == room {
+                (LinkKind::Connection, connected.as_str())
+            } else {
+                (LinkKind::CrossRoom, connected.as_str())
+            }
+        });
+
+        members.chain(connections).collect()
     }
 }
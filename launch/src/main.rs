@@ -15,7 +15,7 @@ fn main() {
     let term2 = Thermometer::from("T01", "Description of T01");
     println!("\n{}", term2.info());
 
-    let mut home = Home::new("home");
+    let home = Home::new("home");
     let room_name_01 = "R01";
     let room_01 = Room::new(room_name_01);
     home.add_room(room_01).unwrap();
@@ -27,7 +27,12 @@ fn main() {
 
     home.print_schema();
 
+    let undo = home.take_snapshot();
     home.remove_device("T01").unwrap();
 
     home.print_schema();
+
+    home.restore_snapshot(undo);
+
+    home.print_schema();
 }